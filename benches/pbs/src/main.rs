@@ -160,6 +160,10 @@ fn get_mock_validator(bench: BenchConfig) -> RelayClient {
         enable_timing_games: false,
         target_first_request_ms: None,
         frequency_get_header_ms: None,
+        max_bid_wei: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        retry_policy: Default::default(),
     };
 
     RelayClient::new(config).unwrap()
@@ -16,5 +16,6 @@ async fn main() -> Result<()> {
 
     let config = StartSignerConfig::load_from_env()?;
     let _guard = initialize_tracing_log(SIGNER_MODULE_NAME);
+    SigningService::init_metrics()?;
     SigningService::run(config).await
 }
@@ -3,8 +3,10 @@ use std::{path::Path, vec};
 use cb_common::{
     config::{
         CommitBoostConfig, LogsSettings, ModuleKind, BUILDER_PORT_ENV, BUILDER_URLS_ENV,
-        CHAIN_SPEC_ENV, CONFIG_DEFAULT, CONFIG_ENV, JWTS_ENV, LOGS_DIR_DEFAULT, LOGS_DIR_ENV,
-        METRICS_PORT_ENV, MODULE_ID_ENV, MODULE_JWT_ENV, PBS_MODULE_NAME, SIGNER_DEFAULT,
+        CHAIN_SPEC_ENV, CONFIG_DEFAULT, CONFIG_ENV, EVENTS_API_JWT_ENV, EXECUTION_JWT_SECRET_ENV,
+        JWTS_ENV, KEYMANAGER_API_TOKEN_ENV, LOGS_DIR_DEFAULT, LOGS_DIR_ENV, METRICS_PORT_ENV,
+        MODULE_ID_ENV, MODULE_JWT_ENV, PBS_MODULE_NAME, RELAY_LIST_FILE_ENV,
+        SIGNER_AUDIT_LOG_DIR_DEFAULT, SIGNER_AUDIT_LOG_ENV, SIGNER_DEFAULT,
         SIGNER_DIR_KEYS_DEFAULT, SIGNER_DIR_KEYS_ENV, SIGNER_DIR_SECRETS, SIGNER_DIR_SECRETS_ENV,
         SIGNER_KEYS_ENV, SIGNER_MODULE_NAME, SIGNER_PORT_ENV, SIGNER_URL_ENV,
     },
@@ -218,6 +220,31 @@ pub fn handle_docker_init(config_path: String, output_dir: String) -> Result<()>
     if let Some((key, val)) = chain_spec_env.clone() {
         pbs_envs.insert(key, val);
     }
+    let relay_list_file_name = cb_config
+        .pbs_global
+        .relay_list_file
+        .as_ref()
+        .and_then(|p| Some((p, p.file_name()?.to_str()?.to_owned())));
+    if let Some((_, file_name)) = &relay_list_file_name {
+        let (key, val) = get_env_val(RELAY_LIST_FILE_ENV, &format!("/{file_name}"));
+        pbs_envs.insert(key, val);
+    }
+    if cb_config.pbs_global.keymanager_api_endpoint.is_some() {
+        // forwarded from the host env, so the token is not written to the compose file
+        let (key, val) = get_env_interp(KEYMANAGER_API_TOKEN_ENV, KEYMANAGER_API_TOKEN_ENV);
+        pbs_envs.insert(key, val);
+    }
+    if cb_config.pbs.pbs_config.execution_client_endpoint.is_some() {
+        // forwarded from the host env, so the secret is not written to the compose file
+        let (key, val) = get_env_interp(EXECUTION_JWT_SECRET_ENV, EXECUTION_JWT_SECRET_ENV);
+        pbs_envs.insert(key, val);
+    }
+    if cb_config.pbs_global.enable_events_api {
+        let jwt = random_jwt();
+        envs.insert(EVENTS_API_JWT_ENV.into(), jwt);
+        let (key, val) = get_env_interp(EVENTS_API_JWT_ENV, EVENTS_API_JWT_ENV);
+        pbs_envs.insert(key, val);
+    }
     if metrics_enabled {
         let (key, val) = get_env_uval(METRICS_PORT_ENV, metrics_port as u64);
         pbs_envs.insert(key, val);
@@ -235,6 +262,9 @@ pub fn handle_docker_init(config_path: String, output_dir: String) -> Result<()>
     // volumes
     let mut pbs_volumes = vec![config_volume.clone()];
     pbs_volumes.extend(chain_spec_volume.clone());
+    if let Some((path, file_name)) = relay_list_file_name {
+        pbs_volumes.push(Volumes::Simple(format!("{}:/{}:ro", path.display(), file_name)));
+    }
     pbs_volumes.extend(get_log_volume(&cb_config.logs, PBS_MODULE_NAME));
 
     // networks
@@ -297,6 +327,30 @@ pub fn handle_docker_init(config_path: String, output_dir: String) -> Result<()>
             let mut volumes = vec![config_volume.clone()];
             volumes.extend(chain_spec_volume.clone());
 
+            // mount the folder of the audit log, so the file is created on the host if
+            // missing
+            if let Some(audit_log_path) = &signer_config.audit_log_path {
+                let file_name = audit_log_path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .expect("audit_log_path should be a file");
+                let dir = match audit_log_path.parent() {
+                    Some(dir) if !dir.as_os_str().is_empty() => dir,
+                    _ => Path::new("."),
+                };
+
+                volumes.push(Volumes::Simple(format!(
+                    "{}:{}",
+                    dir.to_str().expect("could not convert pathbuf to str"),
+                    SIGNER_AUDIT_LOG_DIR_DEFAULT
+                )));
+                let (k, v) = get_env_val(
+                    SIGNER_AUDIT_LOG_ENV,
+                    &format!("{SIGNER_AUDIT_LOG_DIR_DEFAULT}/{file_name}"),
+                );
+                signer_envs.insert(k, v);
+            }
+
             match signer_config.loader {
                 SignerLoader::File { key_path } => {
                     volumes.push(Volumes::Simple(format!("./{}:{}:ro", key_path, SIGNER_DEFAULT)));
@@ -1,4 +1,6 @@
-use cb_common::utils::print_logo;
+use std::path::Path;
+
+use cb_common::{config::CommitBoostConfig, utils::print_logo};
 use clap::{Parser, Subcommand};
 use docker_init::{CB_COMPOSE_FILE, CB_ENV_FILE};
 
@@ -55,6 +57,17 @@ pub enum Command {
         env_path: String,
     },
 
+    /// Load the config and write the effective config back to a TOML file
+    DumpConfig {
+        /// Path to config file
+        #[arg(long("config"))]
+        config_path: String,
+
+        /// Path to output file
+        #[arg(short, long("output"), default_value = "./cb-config.dump.toml")]
+        output_path: String,
+    },
+
     /// See stdout logs
     Logs {
         /// Path to docker compose file
@@ -84,6 +97,10 @@ impl Args {
                 docker_cmd::handle_docker_stop(compose_path, env_path)
             }
 
+            Command::DumpConfig { config_path, output_path } => {
+                CommitBoostConfig::from_file(&config_path)?.write_to_file(Path::new(&output_path))
+            }
+
             Command::Logs { compose_path } => docker_cmd::handle_docker_logs(compose_path),
         }
     }
@@ -1,17 +1,21 @@
 use std::sync::Arc;
 
-use alloy::rpc::types::beacon::BlsSignature;
+use alloy::{primitives::Bytes, rpc::types::beacon::BlsSignature};
 use eyre::WrapErr;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use url::Url;
 
 use super::{
-    constants::{GENERATE_PROXY_KEY_PATH, GET_PUBKEYS_PATH, REQUEST_SIGNATURE_PATH},
+    constants::{
+        GENERATE_PROXY_KEY_PATH, GET_PUBKEYS_PATH, MODULE_API_VERSION, MODULE_API_VERSION_HEADER,
+        REQUEST_SIGNATURE_PATH, VERIFY_SIGNATURE_PATH,
+    },
     error::SignerClientError,
     request::{
         EncryptionScheme, GenerateProxyRequest, GetPubkeysResponse, PublicKey,
-        SignConsensusRequest, SignProxyRequest, SignRequest, SignedProxyDelegation,
+        SignConsensusRequest, SignMetadata, SignProxyRequest, SignRequest, SignRequestWithMetadata,
+        SignedMessage, SignedProxyDelegation, VerifySignatureRequest, VerifySignatureResponse,
     },
 };
 use crate::{
@@ -19,6 +23,7 @@ use crate::{
         schemes::{bls::BlsPublicKey, ecdsa::EcdsaSignature},
         EcdsaPublicKey,
     },
+    types::Chain,
     DEFAULT_REQUEST_TIMEOUT,
 };
 
@@ -39,6 +44,7 @@ impl SignerClient {
             HeaderValue::from_str(&format!("Bearer {}", jwt)).wrap_err("invalid jwt")?;
         auth_value.set_sensitive(true);
         headers.insert(AUTHORIZATION, auth_value);
+        headers.insert(MODULE_API_VERSION_HEADER, HeaderValue::from_static(MODULE_API_VERSION));
         let client = reqwest::Client::builder()
             .timeout(DEFAULT_REQUEST_TIMEOUT)
             .default_headers(headers)
@@ -65,7 +71,7 @@ impl SignerClient {
     }
 
     /// Send a signature request
-    async fn request_signature<T>(&self, request: &SignRequest) -> Result<T, SignerClientError>
+    async fn request_signature<T>(&self, request: &impl Serialize) -> Result<T, SignerClientError>
     where
         T: for<'de> Deserialize<'de>,
     {
@@ -91,21 +97,69 @@ impl SignerClient {
         &self,
         request: SignConsensusRequest,
     ) -> Result<BlsSignature, SignerClientError> {
-        self.request_signature(&request.into()).await
+        self.request_signature(&SignRequest::from(request)).await
     }
 
     pub async fn request_proxy_signature_ecdsa(
         &self,
         request: SignProxyRequest<EcdsaPublicKey>,
     ) -> Result<EcdsaSignature, SignerClientError> {
-        self.request_signature(&request.into()).await
+        self.request_signature(&SignRequest::from(request)).await
     }
 
     pub async fn request_proxy_signature_bls(
         &self,
         request: SignProxyRequest<BlsPublicKey>,
     ) -> Result<BlsSignature, SignerClientError> {
-        self.request_signature(&request.into()).await
+        self.request_signature(&SignRequest::from(request)).await
+    }
+
+    /// Send a signature request together with metadata which the signer logs
+    /// alongside the signing event, for audit purposes
+    pub async fn sign_with_metadata(
+        &self,
+        request: SignRequest,
+        metadata: SignMetadata,
+    ) -> Result<SignedMessage, SignerClientError> {
+        let request = SignRequestWithMetadata { request, metadata: Some(metadata) };
+
+        match request.request {
+            SignRequest::Consensus(_) | SignRequest::ProxyBls(_) => {
+                self.request_signature(&request).await.map(SignedMessage::Bls)
+            }
+            SignRequest::ProxyEcdsa(_) => {
+                self.request_signature(&request).await.map(SignedMessage::Ecdsa)
+            }
+        }
+    }
+
+    /// Verify a BLS signature over `msg`, the 32 bytes root of the signed
+    /// object, using the same signing domain as the signer on `chain`.
+    /// Returns whether the signature is valid
+    pub async fn verify_signature(
+        &self,
+        pubkey: BlsPublicKey,
+        msg: Bytes,
+        signature: BlsSignature,
+        chain: Chain,
+    ) -> Result<bool, SignerClientError> {
+        let url = self.url.join(VERIFY_SIGNATURE_PATH)?;
+        let request = VerifySignatureRequest::new(pubkey, msg, signature, chain);
+        let res = self.client.post(url).json(&request).send().await?;
+
+        let status = res.status();
+        let response_bytes = res.bytes().await?;
+
+        if !status.is_success() {
+            return Err(SignerClientError::FailedRequest {
+                status: status.as_u16(),
+                error_msg: String::from_utf8_lossy(&response_bytes).into_owned(),
+            });
+        }
+
+        let response: VerifySignatureResponse = serde_json::from_slice(&response_bytes)?;
+
+        Ok(response.valid)
     }
 
     async fn generate_proxy_key<T>(
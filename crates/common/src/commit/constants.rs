@@ -1,3 +1,11 @@
 pub const GET_PUBKEYS_PATH: &str = "/signer/v1/get_pubkeys";
 pub const REQUEST_SIGNATURE_PATH: &str = "/signer/v1/request_signature";
 pub const GENERATE_PROXY_KEY_PATH: &str = "/signer/v1/generate_proxy_key";
+pub const VERIFY_SIGNATURE_PATH: &str = "/signer/v1/verify_signature";
+
+/// Module API version implemented by this crate
+pub const MODULE_API_VERSION: &str = "0.1.0";
+/// Header used by modules to advertise the module API version they implement
+pub const MODULE_API_VERSION_HEADER: &str = "CB-Module-API-Version";
+/// Header returned when a module advertises an incompatible API version
+pub const REQUIRED_API_VERSION_HEADER: &str = "CB-Required-API-Version";
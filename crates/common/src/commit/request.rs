@@ -1,17 +1,21 @@
 use std::fmt::{self, Debug, Display, LowerHex};
 
-use alloy::rpc::types::beacon::BlsSignature;
+use alloy::{primitives::Bytes, rpc::types::beacon::BlsSignature};
 use derive_more::derive::From;
 use serde::{Deserialize, Serialize};
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
+use uuid::Uuid;
 
 use crate::{
     constants::COMMIT_BOOST_DOMAIN,
     error::BlstErrorWrapper,
     signature::verify_signed_message,
-    signer::schemes::{bls::BlsPublicKey, ecdsa::EcdsaPublicKey},
-    types::Chain,
+    signer::schemes::{
+        bls::BlsPublicKey,
+        ecdsa::{EcdsaPublicKey, EcdsaSignature},
+    },
+    types::{Chain, ModuleId},
 };
 
 pub trait PublicKey: AsRef<[u8]> + Debug + Clone + Copy + TreeHash + Display + LowerHex {}
@@ -75,6 +79,53 @@ pub enum SignRequest {
     ProxyEcdsa(SignProxyRequest<EcdsaPublicKey>),
 }
 
+/// Additional context attached to a signature request. The signer logs it
+/// alongside the signing event so that signatures can be audited later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignMetadata {
+    /// Id generated by the module to correlate the request on both sides
+    pub request_id: Uuid,
+    pub module_id: ModuleId,
+    /// Free-form description of why the signature is requested
+    pub purpose: String,
+    /// Slot the signature is for. The signer logs the current slot if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+}
+
+impl SignMetadata {
+    pub fn new(module_id: ModuleId, purpose: impl Into<String>) -> Self {
+        Self { request_id: Uuid::new_v4(), module_id, purpose: purpose.into(), slot: None }
+    }
+
+    pub fn with_slot(self, slot: u64) -> Self {
+        Self { slot: Some(slot), ..self }
+    }
+}
+
+/// Body of a request to the signer. Metadata is optional so that plain
+/// `SignRequest` bodies are still accepted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignRequestWithMetadata {
+    #[serde(flatten)]
+    pub request: SignRequest,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<SignMetadata>,
+}
+
+impl From<SignRequest> for SignRequestWithMetadata {
+    fn from(request: SignRequest) -> Self {
+        Self { request, metadata: None }
+    }
+}
+
+/// Signature returned by the signer, the scheme depends on the type of request
+#[derive(Debug, Clone)]
+pub enum SignedMessage {
+    Bls(BlsSignature),
+    Ecdsa(EcdsaSignature),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignConsensusRequest {
     pub pubkey: BlsPublicKey,
@@ -146,6 +197,27 @@ impl GenerateProxyRequest {
     }
 }
 
+/// Request to verify a BLS signature over `msg`, the root of the signed
+/// object, computed with the same signing domain used by the signer on `chain`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifySignatureRequest {
+    pub pubkey: BlsPublicKey,
+    pub msg: Bytes,
+    pub signature: BlsSignature,
+    pub chain: Chain,
+}
+
+impl VerifySignatureRequest {
+    pub fn new(pubkey: BlsPublicKey, msg: Bytes, signature: BlsSignature, chain: Chain) -> Self {
+        Self { pubkey, msg, signature, chain }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct VerifySignatureResponse {
+    pub valid: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GetPubkeysResponse {
     pub keys: Vec<ConsensusProxyMap>,
@@ -164,3 +236,32 @@ impl ConsensusProxyMap {
         Self { consensus, proxy_bls: vec![], proxy_ecdsa: vec![] }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_request_metadata_is_optional() {
+        let request =
+            SignRequest::Consensus(SignConsensusRequest::builder(BlsPublicKey::default()));
+
+        let plain = serde_json::to_value(&request).unwrap();
+        let parsed: SignRequestWithMetadata = serde_json::from_value(plain.clone()).unwrap();
+        assert!(matches!(parsed.request, SignRequest::Consensus(_)));
+        assert!(parsed.metadata.is_none());
+
+        let metadata =
+            SignMetadata::new(ModuleId("TEST_MODULE".to_string()), "preconf").with_slot(10);
+        let with_metadata = SignRequestWithMetadata { request, metadata: Some(metadata.clone()) };
+        let value = serde_json::to_value(&with_metadata).unwrap();
+        assert_eq!(value["type"], plain["type"]);
+
+        let parsed: SignRequestWithMetadata = serde_json::from_value(value).unwrap();
+        let parsed_metadata = parsed.metadata.unwrap();
+        assert_eq!(parsed_metadata.request_id, metadata.request_id);
+        assert_eq!(parsed_metadata.module_id, metadata.module_id);
+        assert_eq!(parsed_metadata.purpose, "preconf");
+        assert_eq!(parsed_metadata.slot, Some(10));
+    }
+}
@@ -7,6 +7,9 @@ pub const CONFIG_DEFAULT: &str = "/cb-config.toml";
 /// Path to the chain spec file
 pub const CHAIN_SPEC_ENV: &str = "CB_CHAIN_SPEC";
 
+/// Chain name to use if `chain` is missing from the config, e.g. "holesky"
+pub const CHAIN_ENV: &str = "CB_CHAIN";
+
 /// Where to receive scrape requests from Prometheus
 pub const METRICS_PORT_ENV: &str = "CB_METRICS_PORT";
 
@@ -22,6 +25,19 @@ pub const PBS_MODULE_NAME: &str = "pbs";
 /// Urls the pbs modules should post events to (comma separated)
 pub const BUILDER_URLS_ENV: &str = "CB_BUILDER_URLS";
 
+/// Path to the relay list file, overrides `relay_list_file` from the config
+pub const RELAY_LIST_FILE_ENV: &str = "CB_RELAY_LIST_FILE";
+
+/// Bearer token to authenticate with the keymanager API, if required
+pub const KEYMANAGER_API_TOKEN_ENV: &str = "CB_KEYMANAGER_API_TOKEN";
+
+/// Hex encoded secret shared with the execution client to authenticate with
+/// its engine API, e.g. the content of its `jwt.hex` file
+pub const EXECUTION_JWT_SECRET_ENV: &str = "CB_EXECUTION_JWT_SECRET";
+
+/// JWT that clients of the events API must authenticate with
+pub const EVENTS_API_JWT_ENV: &str = "CB_EVENTS_API_JWT";
+
 ///////////////////////// SIGNER /////////////////////////
 
 pub const SIGNER_IMAGE_DEFAULT: &str = "ghcr.io/commit-boost/signer:latest";
@@ -42,6 +58,9 @@ pub const SIGNER_DIR_KEYS_DEFAULT: &str = "/keys";
 /// Path to `secrets` folder
 pub const SIGNER_DIR_SECRETS_ENV: &str = "CB_SIGNER_LOADER_SECRETS_DIR";
 pub const SIGNER_DIR_SECRETS: &str = "/secrets";
+/// Path to the audit log file
+pub const SIGNER_AUDIT_LOG_ENV: &str = "CB_SIGNER_AUDIT_LOG_FILE";
+pub const SIGNER_AUDIT_LOG_DIR_DEFAULT: &str = "/audit_logs";
 
 ///////////////////////// MODULES /////////////////////////
 
@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Enables the constraints API
+pub const FF_CONSTRAINTS: &str = "constraints_api";
+/// Enables fetching headers from relays ahead of the validator request
+pub const FF_SPECULATIVE_FETCHING: &str = "speculative_fetching";
+
+/// Runtime toggles for features that are being gradually rolled out. Flags
+/// not present in the config are disabled
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct FeatureFlags(pub HashMap<String, bool>);
+
+impl FeatureFlags {
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.0.get(name).copied().unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feature_flags() {
+        let flags: FeatureFlags = toml::from_str(
+            r#"
+            constraints_api = true
+            speculative_fetching = false
+            "#,
+        )
+        .unwrap();
+
+        assert!(flags.is_enabled(FF_CONSTRAINTS));
+        assert!(!flags.is_enabled(FF_SPECULATIVE_FETCHING));
+        assert!(!flags.is_enabled("unknown"));
+        assert!(!FeatureFlags::default().is_enabled(FF_CONSTRAINTS));
+    }
+}
@@ -1,11 +1,17 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use eyre::Result;
+use eyre::{ensure, Result, WrapErr};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-use crate::types::{load_chain_from_file, Chain};
+use crate::{
+    commit::constants::MODULE_API_VERSION,
+    types::{load_chain_from_file, Chain},
+    utils::{default_bool, parse_semver},
+};
 
 mod constants;
+mod features;
 mod log;
 mod metrics;
 mod module;
@@ -14,6 +20,7 @@ mod signer;
 mod utils;
 
 pub use constants::*;
+pub use features::*;
 pub use log::*;
 pub use metrics::*;
 pub use module::*;
@@ -30,21 +37,67 @@ pub struct CommitBoostConfig {
     pub signer: Option<SignerConfig>,
     pub metrics: Option<MetricsConfig>,
     pub logs: Option<LogsSettings>,
+    /// Whether to bind the PBS and signer servers to IPv6 (dual-stack)
+    #[serde(default = "default_bool::<false>")]
+    pub enable_ipv6: bool,
+    /// Module API version (semver) that modules need to be compatible with
+    /// to be served by the signer
+    #[serde(default = "default_module_api_version")]
+    pub module_api_version: String,
+    /// Toggles for features that are being gradually rolled out, e.g.
+    /// `constraints_api = true`
+    #[serde(default)]
+    pub feature_flags: FeatureFlags,
+    /// Top-level settings of the PBS module
+    #[serde(flatten)]
+    pub pbs_global: PbsGlobalConfig,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ConfigError {
+    #[error("relay_timeout_ms out of range: min: {min_ms} max: {max_ms} got: {timeout_ms}")]
+    TimeoutOutOfRange { timeout_ms: u64, min_ms: u64, max_ms: u64 },
+}
+
+fn default_module_api_version() -> String {
+    MODULE_API_VERSION.to_string()
 }
 
 impl CommitBoostConfig {
     /// Validate config
     pub fn validate(&self) -> Result<()> {
-        self.pbs.pbs_config.validate()?;
+        self.pbs.pbs_config.validate(self.chain, &self.pbs_global, &self.relays)?;
+
+        ensure!(
+            parse_semver(&self.module_api_version).is_some(),
+            "module_api_version must be in the form MAJOR.MINOR.PATCH, got {}",
+            self.module_api_version
+        );
+
         Ok(())
     }
 
     pub fn from_file(path: &str) -> Result<Self> {
-        let config: Self = load_from_file(path)?;
+        let config: Self = load_config_from_file(path)?;
         config.validate()?;
         Ok(config)
     }
 
+    /// Parse the config from YAML, with the same fields as the TOML format
+    pub fn from_yaml(yaml_str: &str) -> Result<Self> {
+        let config: Self = load_config_from_yaml(yaml_str)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Serialize the config to TOML and write it to `path`, e.g. to inspect the
+    /// effective config after it has been loaded
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        let config = toml::to_string_pretty(self).wrap_err("could not serialize config to toml")?;
+        std::fs::write(path, config)
+            .wrap_err(format!("Unable to write config file: {}", path.display()))
+    }
+
     // When loading the config from the environment, it's important that every path
     // is replaced with the correct value if the config is loaded inside a container
     pub fn from_env_path() -> Result<Self> {
@@ -61,6 +114,10 @@ impl CommitBoostConfig {
                 signer: rest_config.signer,
                 metrics: rest_config.metrics,
                 logs: rest_config.logs,
+                enable_ipv6: rest_config.enable_ipv6,
+                module_api_version: rest_config.module_api_version,
+                feature_flags: rest_config.feature_flags,
+                pbs_global: rest_config.pbs_global,
             }
         } else {
             load_file_from_env(CONFIG_ENV)?
@@ -94,4 +151,12 @@ struct HelperConfig {
     signer: Option<SignerConfig>,
     metrics: Option<MetricsConfig>,
     logs: Option<LogsSettings>,
+    #[serde(default = "default_bool::<false>")]
+    enable_ipv6: bool,
+    #[serde(default = "default_module_api_version")]
+    module_api_version: String,
+    #[serde(default)]
+    feature_flags: FeatureFlags,
+    #[serde(flatten)]
+    pbs_global: PbsGlobalConfig,
 }
@@ -10,7 +10,7 @@ use crate::{
         constants::{CONFIG_ENV, MODULE_ID_ENV, MODULE_JWT_ENV, SIGNER_URL_ENV},
         load_env_var,
         utils::load_file_from_env,
-        BUILDER_PORT_ENV,
+        FeatureFlags, BUILDER_PORT_ENV,
     },
     types::{Chain, Jwt, ModuleId},
 };
@@ -48,6 +48,8 @@ pub struct StartCommitModuleConfig<T = ()> {
     pub chain: Chain,
     /// Signer client to call Signer API
     pub signer_client: SignerClient,
+    /// Feature flags
+    pub feature_flags: FeatureFlags,
     /// Opaque module config
     pub extra: T,
 }
@@ -82,6 +84,8 @@ pub fn load_commit_module_config<T: DeserializeOwned>() -> Result<StartCommitMod
     struct StubConfig<U> {
         chain: Chain,
         modules: Vec<ThisModule<U>>,
+        #[serde(default)]
+        feature_flags: FeatureFlags,
     }
 
     // load module config including the extra data (if any)
@@ -110,6 +114,7 @@ pub fn load_commit_module_config<T: DeserializeOwned>() -> Result<StartCommitMod
         id: module_config.static_config.id,
         chain: cb_config.chain,
         signer_client,
+        feature_flags: cb_config.feature_flags,
         extra: module_config.extra,
     })
 }
@@ -1,19 +1,42 @@
 //! Configuration for the PBS module
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
-use alloy::primitives::U256;
-use eyre::Result;
+use alloy::{
+    primitives::{B256, U256},
+    rpc::types::beacon::BlsPublicKey,
+};
+use eyre::{ensure, Result, WrapErr};
+use rand::Rng;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use url::Url;
+use tracing::warn;
+use url::{Host, Url};
 
-use super::{constants::PBS_IMAGE_DEFAULT, CommitBoostConfig};
+use super::{constants::PBS_IMAGE_DEFAULT, CommitBoostConfig, ConfigError};
 use crate::{
     commit::client::SignerClient,
-    config::{load_env_var, load_file_from_env, CONFIG_ENV, MODULE_JWT_ENV, SIGNER_URL_ENV},
-    pbs::{BuilderEventPublisher, DefaultTimeout, RelayClient, RelayEntry, LATE_IN_SLOT_TIME_MS},
-    types::Chain,
-    utils::{as_eth_str, default_bool, default_u256, default_u64},
+    config::{
+        load_env_var, load_file_from_env, load_optional_env_var, FeatureFlags, CONFIG_ENV,
+        EVENTS_API_JWT_ENV, EXECUTION_JWT_SECRET_ENV, KEYMANAGER_API_TOKEN_ENV, MODULE_JWT_ENV,
+        RELAY_LIST_FILE_ENV, SIGNER_URL_ENV,
+    },
+    pbs::{
+        error::PbsError, BeaconApiClient, BuilderEventPublisher, DefaultTimeout,
+        ExecutionApiClient, KeymanagerClient, RelayClient, RelayEntry, BEACON_CLIENT_TIMEOUT_MS,
+        HEARTBEAT_INTERVAL_SECS, KEYMANAGER_REFRESH_SECS, LATE_IN_SLOT_TIME_MS, MAX_SIZE,
+        MAX_SLOT_LOOKAHEAD, MIN_RELAY_TIMEOUT_MS, RELAY_EXCLUSION_LIST_REFRESH_SECS,
+        SIMULATE_BID_VALUE_GWEI,
+    },
+    types::{Chain, Jwt},
+    utils::{
+        as_eth_str, as_opt_eth_str, default_bool, default_u256, default_u64, default_u8,
+        default_user_agent, default_usize,
+    },
 };
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -32,6 +55,83 @@ pub struct RelayConfig {
     pub target_first_request_ms: Option<u64>,
     /// Frequency in ms to send get_header requests
     pub frequency_get_header_ms: Option<u64>,
+    /// Maximum bid that will be accepted from this relay, higher bids are
+    /// considered invalid
+    #[serde(
+        rename = "max_bid_eth",
+        with = "as_opt_eth_str",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_bid_wei: Option<U256>,
+    /// Timeout in ms to establish a connection with the relay, including DNS
+    /// resolution and TLS handshake
+    pub connect_timeout_ms: Option<u64>,
+    /// Timeout in ms for a full request to the relay. Endpoint specific
+    /// timeouts (e.g. `timeout_get_header_ms`) take precedence
+    pub request_timeout_ms: Option<u64>,
+    /// How to retry failed requests to idempotent endpoints
+    #[serde(default)]
+    pub retry_policy: RelayRetryPolicy,
+}
+
+/// Retry policy for requests to idempotent relay endpoints (e.g. `get_status`,
+/// `register_validator`). Only timeouts and 5xx responses are retried
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct RelayRetryPolicy {
+    /// Max number of retries after the first attempt, 0 to disable retries
+    #[serde(default = "default_u64::<0>")]
+    pub max_retries: u64,
+    /// Backoff before the first retry in milliseconds, doubled at each retry.
+    /// Up to half of the backoff is randomly subtracted as jitter
+    #[serde(default = "default_u64::<100>")]
+    pub initial_backoff_ms: u64,
+    /// Max backoff between retries in milliseconds
+    #[serde(default = "default_u64::<1000>")]
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RelayRetryPolicy {
+    fn default() -> Self {
+        Self { max_retries: 0, initial_backoff_ms: 100, max_backoff_ms: 1000 }
+    }
+}
+
+impl RelayRetryPolicy {
+    /// Jittered backoff before the `retry`-th retry, starting from 1
+    pub fn backoff(&self, retry: u64) -> Duration {
+        let doublings = retry.saturating_sub(1).min(63) as u32;
+        let backoff_ms =
+            self.initial_backoff_ms.saturating_mul(1 << doublings).min(self.max_backoff_ms);
+        let jitter_ms = rand::thread_rng().gen_range(0..=backoff_ms / 2);
+        Duration::from_millis(backoff_ms - jitter_ms)
+    }
+}
+
+/// Settings of the HTTP clients used to send requests to relays
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PbsClientConfig {
+    /// Timeout in ms to establish a connection, for relays without a
+    /// `connect_timeout_ms`
+    #[serde(default = "default_u64::<{ DefaultTimeout::RELAY_CONNECT_MS }>")]
+    pub connect_timeout_ms: u64,
+    /// Max size of a relay response in bytes, larger responses are rejected
+    #[serde(default = "default_usize::<MAX_SIZE>")]
+    pub max_payload_size_bytes: usize,
+    /// User agent sent to relays, followed by the user agent of the beacon
+    /// node request
+    #[serde(default = "default_user_agent")]
+    pub user_agent: String,
+}
+
+impl Default for PbsClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_ms: DefaultTimeout::RELAY_CONNECT_MS,
+            max_payload_size_bytes: MAX_SIZE,
+            user_agent: default_user_agent(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -41,6 +141,9 @@ pub struct PbsConfig {
     /// Whether to forward `get_status` to relays or skip it
     #[serde(default = "default_bool::<true>")]
     pub relay_check: bool,
+    /// Timeout for relay requests in milliseconds, for relays without a
+    /// `request_timeout_ms`. Must be between 50 and the slot time
+    pub relay_timeout_ms: u64,
     /// Timeout for get_header request in milliseconds
     #[serde(default = "default_u64::<{ DefaultTimeout::GET_HEADER_MS }>")]
     pub timeout_get_header_ms: u64,
@@ -56,17 +159,178 @@ pub struct PbsConfig {
     /// Minimum bid that will be accepted from get_header
     #[serde(rename = "min_bid_eth", with = "as_eth_str", default = "default_u256")]
     pub min_bid_wei: U256,
+    /// Max deviation of a bid gas limit from the expected one, in basis
+    /// points. The expected gas limit moves from the parent gas limit toward
+    /// the one registered by the validator, by at most 1/1024 of the parent
+    /// gas limit. Bids outside the tolerance are rejected
+    pub gas_limit_tolerance_bps: Option<u64>,
     /// List of relay monitor urls in the form of scheme://host
     #[serde(default)]
     pub relay_monitors: Vec<Url>,
     /// How late in the slot we consider to be "late"
     #[serde(default = "default_u64::<LATE_IN_SLOT_TIME_MS>")]
     pub late_in_slot_time_ms: u64,
+    /// How many slots ahead of the current slot get_header can be requested
+    /// for
+    #[serde(default = "default_u8::<MAX_SLOT_LOOKAHEAD>")]
+    pub max_slot_lookahead: u8,
+    /// Beacon API endpoint of the consensus client, used to verify that it's
+    /// on the configured chain and to query proposer duties
+    pub consensus_client_endpoint: Option<Url>,
+    /// Static list of the managed validator pubkeys, alternative to
+    /// `consensus_client_endpoint` for setups without a reachable beacon API.
+    /// Only registrations for these validators are forwarded to relays
+    #[serde(default)]
+    pub validator_pubkeys: Vec<BlsPublicKey>,
+    /// JSON-RPC endpoint of the execution client, used to check that the
+    /// parent hash of requested headers is a known block
+    pub execution_client_endpoint: Option<Url>,
+    /// Whether relay urls must use https. Defaults to true on mainnet and false
+    /// on other chains
+    pub require_https: Option<bool>,
+    /// Url of a JSON list of relay urls to exclude, e.g. a community
+    /// maintained list of problematic relays
+    pub relay_exclusion_list_url: Option<Url>,
+    /// How often to refresh the relay exclusion list in seconds
+    #[serde(default = "default_u64::<RELAY_EXCLUSION_LIST_REFRESH_SECS>")]
+    pub relay_exclusion_list_refresh_secs: u64,
+    /// Whether to return synthetic bids and payloads instead of contacting the
+    /// relays, for testing the validator setup without a live relay. Not
+    /// allowed on mainnet
+    #[serde(default = "default_bool::<false>")]
+    pub simulate_mode: bool,
+    /// Value of the synthetic bids returned in simulate mode, in gwei
+    #[serde(default = "default_u64::<SIMULATE_BID_VALUE_GWEI>")]
+    pub simulate_bid_value_gwei: u64,
+    /// What to do when a startup health check fails. Defaults to strict on
+    /// mainnet and warn on other chains
+    pub health_check_on_startup: Option<HealthCheckOnStartup>,
+    /// Settings of the HTTP clients used for relay requests
+    #[serde(default)]
+    pub client: PbsClientConfig,
+}
+
+/// Behaviour when a startup health check, e.g. the consensus client chain
+/// check, fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthCheckOnStartup {
+    /// Refuse to start
+    Strict,
+    /// Log the failure and start anyway
+    Warn,
+    /// Don't run the checks
+    Skip,
 }
 
 impl PbsConfig {
-    /// Validate PBS config parameters
-    pub fn validate(&self) -> Result<()> {
+    /// Validate PBS config parameters, together with the top-level PBS
+    /// settings and the relays of the config file
+    pub fn validate(
+        &self,
+        chain: Chain,
+        pbs_global: &PbsGlobalConfig,
+        relays: &[RelayConfig],
+    ) -> Result<()> {
+        let max_timeout_ms = chain.slot_time_sec() * 1000;
+        ensure!(
+            (MIN_RELAY_TIMEOUT_MS..=max_timeout_ms).contains(&self.relay_timeout_ms),
+            ConfigError::TimeoutOutOfRange {
+                timeout_ms: self.relay_timeout_ms,
+                min_ms: MIN_RELAY_TIMEOUT_MS,
+                max_ms: max_timeout_ms,
+            }
+        );
+
+        ensure!(
+            self.relay_exclusion_list_refresh_secs > 0,
+            "relay_exclusion_list_refresh_secs must be greater than 0"
+        );
+
+        ensure!(
+            self.consensus_client_endpoint.is_none() || self.validator_pubkeys.is_empty(),
+            "consensus_client_endpoint and validator_pubkeys can't be set at the same time"
+        );
+
+        ensure!(
+            self.client.max_payload_size_bytes > 0,
+            "client.max_payload_size_bytes must be greater than 0"
+        );
+
+        ensure!(
+            pbs_global.signing_domain_override.is_none() || !chain.is_mainnet(),
+            "signing_domain_override is not allowed on mainnet"
+        );
+
+        ensure!(
+            !self.simulate_mode || !chain.is_mainnet(),
+            "simulate_mode is not allowed on mainnet"
+        );
+
+        ensure!(
+            pbs_global.heartbeat_interval_secs > 0,
+            "heartbeat_interval_secs must be greater than 0"
+        );
+
+        if pbs_global.relay_duplicate_check {
+            check_duplicate_relays(relays)?;
+        }
+
+        // the keymanager API replaces the static `validator_pubkeys`. It can be
+        // combined with the validator status checks of the consensus client, in
+        // which case registrations must pass both
+        if pbs_global.keymanager_api_endpoint.is_some() {
+            ensure!(
+                self.validator_pubkeys.is_empty(),
+                "keymanager_api_endpoint and validator_pubkeys can't be set at the same time"
+            );
+            ensure!(
+                pbs_global.keymanager_refresh_secs > 0,
+                "keymanager_refresh_secs must be greater than 0"
+            );
+        }
+
+        // beacon API requests should fail within the `get_header` deadline, so a
+        // stalled consensus client can't make `get_header` time out
+        if self.consensus_client_endpoint.is_some() {
+            let beacon_client_timeout_ms = pbs_global.beacon_client_timeout_ms;
+            ensure!(
+                beacon_client_timeout_ms > 0,
+                "beacon_client_timeout_ms must be greater than 0"
+            );
+            ensure!(
+                beacon_client_timeout_ms < self.timeout_get_header_ms,
+                "beacon_client_timeout_ms ({beacon_client_timeout_ms}) must be shorter than \
+                 timeout_get_header_ms ({})",
+                self.timeout_get_header_ms
+            );
+        }
+
+        if pbs_global.strict_mode {
+            self.validate_strict()?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether relay urls must use https on the given chain
+    pub fn is_https_required(&self, chain: Chain) -> bool {
+        self.require_https.unwrap_or(chain.is_mainnet())
+    }
+
+    /// Startup health check behaviour on the given chain
+    pub fn startup_health_check(&self, chain: Chain) -> HealthCheckOnStartup {
+        self.health_check_on_startup.unwrap_or(if chain.is_mainnet() {
+            HealthCheckOnStartup::Strict
+        } else {
+            HealthCheckOnStartup::Warn
+        })
+    }
+
+    /// Validate PBS config parameters when running in strict mode
+    pub fn validate_strict(&self) -> Result<()> {
+        ensure!(!self.skip_sigverify, "skip_sigverify can't be enabled in strict mode");
+        ensure!(!self.simulate_mode, "simulate_mode can't be enabled in strict mode");
         Ok(())
     }
 }
@@ -85,6 +349,83 @@ pub struct StaticPbsConfig {
     pub with_signer: bool,
 }
 
+/// Settings of the PBS module at the root of the config file, next to `chain`
+/// and `relays`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PbsGlobalConfig {
+    /// Whether to enable all optional validations:
+    /// - `skip_sigverify` and `simulate_mode` are rejected at startup
+    /// - submitted blinded blocks for a slot other than the one of the last
+    ///   `get_header` request are rejected, instead of only logged
+    /// - `health_check_on_startup` is always "strict"
+    pub strict_mode: bool,
+    /// Name of the relay set, used to tag logs and relay metrics when running
+    /// multiple instances with different relays
+    pub relay_set_name: Option<String>,
+    /// Signing domain to use for builder signatures instead of the one
+    /// derived from the genesis fork version, e.g. to test custom domains on
+    /// devnets. Not allowed on mainnet
+    pub signing_domain_override: Option<B256>,
+    /// Interval in seconds between heartbeats sent to `heartbeat_url`
+    pub heartbeat_interval_secs: u64,
+    /// URL to periodically POST a heartbeat to, to signal that the PBS module
+    /// is alive
+    pub heartbeat_url: Option<Url>,
+    /// File to write the PID of the PBS module to at startup, removed on
+    /// graceful shutdown
+    pub pid_file: Option<PathBuf>,
+    /// TOML or JSON file with additional relay entries, merged with `relays`.
+    /// The file is reloaded on SIGHUP
+    pub relay_list_file: Option<PathBuf>,
+    /// Whether relays on loopback addresses can use http when
+    /// `require_https` is enabled. Defaults to false on mainnet and true on
+    /// other chains
+    pub allow_plaintext_on_loopback: Option<bool>,
+    /// Whether relays configured more than once with the same url are
+    /// rejected. If disabled, duplicates are dropped with a warning
+    pub relay_duplicate_check: bool,
+    /// Keymanager API of the validator client, to fetch the managed
+    /// validator pubkeys from instead of the static `validator_pubkeys`
+    pub keymanager_api_endpoint: Option<Url>,
+    /// Interval in seconds between refreshes of the managed validator pubkeys
+    /// from `keymanager_api_endpoint`
+    pub keymanager_refresh_secs: u64,
+    /// Timeout in ms for requests to `consensus_client_endpoint`. Must be
+    /// shorter than `timeout_get_header_ms`
+    pub beacon_client_timeout_ms: u64,
+    /// Whether to stream builder events to clients as server-sent events,
+    /// authenticated with the JWT in `CB_EVENTS_API_JWT`
+    pub enable_events_api: bool,
+}
+
+impl Default for PbsGlobalConfig {
+    fn default() -> Self {
+        Self {
+            strict_mode: false,
+            relay_set_name: None,
+            signing_domain_override: None,
+            heartbeat_interval_secs: HEARTBEAT_INTERVAL_SECS,
+            heartbeat_url: None,
+            pid_file: None,
+            relay_list_file: None,
+            allow_plaintext_on_loopback: None,
+            relay_duplicate_check: true,
+            keymanager_api_endpoint: None,
+            keymanager_refresh_secs: KEYMANAGER_REFRESH_SECS,
+            beacon_client_timeout_ms: BEACON_CLIENT_TIMEOUT_MS,
+            enable_events_api: false,
+        }
+    }
+}
+
+impl PbsGlobalConfig {
+    /// Whether relays on loopback addresses can use http on the given chain
+    pub fn is_plaintext_on_loopback_allowed(&self, chain: Chain) -> bool {
+        self.allow_plaintext_on_loopback.unwrap_or(!chain.is_mainnet())
+    }
+}
+
 /// Runtime config for the pbs module
 #[derive(Debug, Clone)]
 pub struct PbsModuleConfig {
@@ -92,12 +433,131 @@ pub struct PbsModuleConfig {
     pub chain: Chain,
     /// Pbs default config
     pub pbs_config: Arc<PbsConfig>,
-    /// List of relays
+    /// List of relays from the main config, see `relay_list_file` for
+    /// additional ones
     pub relays: Vec<RelayClient>,
     /// Signer client to call Signer API
     pub signer_client: Option<SignerClient>,
     /// Event publisher
     pub event_publiher: Option<BuilderEventPublisher>,
+    /// Whether to bind the server to IPv6 (dual-stack)
+    pub enable_ipv6: bool,
+    /// Top-level PBS settings. `relay_list_file` is resolved from the env if
+    /// running in a container
+    pub pbs_global: PbsGlobalConfig,
+    /// Client to query the consensus client, if an endpoint is configured
+    pub beacon_client: Option<BeaconApiClient>,
+    /// Client to query the execution client, if an endpoint is configured
+    pub execution_client: Option<ExecutionApiClient>,
+    /// Feature flags
+    pub feature_flags: FeatureFlags,
+    /// When the module was started
+    pub uptime_start: SystemTime,
+    /// Client to query the keymanager API, if an endpoint is configured
+    pub keymanager_client: Option<KeymanagerClient>,
+    /// JWT required to connect to the events API, set if it's enabled
+    pub events_api_jwt: Option<Jwt>,
+}
+
+impl PbsModuleConfig {
+    /// Startup health check behaviour, always strict in strict mode
+    pub fn startup_health_check(&self) -> HealthCheckOnStartup {
+        if self.pbs_global.strict_mode {
+            return HealthCheckOnStartup::Strict;
+        }
+
+        self.pbs_config.startup_health_check(self.chain)
+    }
+
+    /// Signing domain used to verify builder signatures, e.g. on relay bids
+    pub fn builder_domain(&self) -> [u8; 32] {
+        match self.pbs_global.signing_domain_override {
+            Some(domain) => domain.0,
+            None => self.chain.builder_domain(),
+        }
+    }
+
+    /// Relays from the main config merged with the ones from
+    /// `relay_list_file`. Entries with a url that is already configured are
+    /// skipped
+    pub fn load_relays_with_list_file(&self) -> Result<Vec<RelayClient>> {
+        let Some(path) = &self.pbs_global.relay_list_file else {
+            return Ok(self.relays.clone());
+        };
+
+        let require_https = self.pbs_config.is_https_required(self.chain);
+        let file_relays = load_relay_clients(
+            load_relay_list_file(path)?,
+            &self.pbs_global.relay_set_name,
+            self.pbs_config.relay_timeout_ms,
+            &self.pbs_config.client,
+            require_https,
+            self.pbs_global.is_plaintext_on_loopback_allowed(self.chain),
+        )?;
+
+        let mut relays = self.relays.clone();
+        for relay in file_relays {
+            if relays.iter().all(|r| r.config.entry.url != relay.config.entry.url) {
+                relays.push(relay);
+            }
+        }
+
+        Ok(relays)
+    }
+}
+
+/// Loads relay entries from a JSON file with an array of entries, or from a
+/// TOML file with `[[relays]]` tables as in the main config
+pub fn load_relay_list_file(path: &Path) -> Result<Vec<RelayConfig>> {
+    #[derive(Deserialize)]
+    struct RelayListFile {
+        relays: Vec<RelayConfig>,
+    }
+
+    let file = std::fs::read_to_string(path)
+        .wrap_err(format!("Unable to find relay list file: {}", path.display()))?;
+
+    if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&file).wrap_err("could not decode relay list json")
+    } else {
+        let decoded: RelayListFile =
+            toml::from_str(&file).wrap_err("could not decode relay list toml")?;
+        Ok(decoded.relays)
+    }
+}
+
+/// Whether the url points to the local machine, e.g. http://127.0.0.1:18550
+fn is_loopback(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(Host::Ipv6(ip)) => ip.is_loopback(),
+        Some(Host::Domain(domain)) => domain == "localhost",
+        None => false,
+    }
+}
+
+/// Builds the keymanager client, with the bearer token from the env if set
+fn load_execution_client(url: Url) -> Result<ExecutionApiClient> {
+    let client = ExecutionApiClient::new(url)?;
+    // docker compose sets it to an empty string if it's not set on the host
+    let secret = load_optional_env_var(EXECUTION_JWT_SECRET_ENV).unwrap_or_default();
+    if secret.trim().is_empty() {
+        return Ok(client);
+    }
+
+    let secret: B256 =
+        secret.trim().parse().wrap_err(format!("invalid {EXECUTION_JWT_SECRET_ENV}"))?;
+    Ok(client.with_jwt_secret(secret))
+}
+
+fn load_keymanager_client(url: Url) -> Result<KeymanagerClient> {
+    let token = std::env::var(KEYMANAGER_API_TOKEN_ENV).ok();
+    KeymanagerClient::new(url, token)
+}
+
+/// Path of the relay list file, from the env if running in a container
+fn relay_list_file_path(config_path: Option<PathBuf>) -> Option<PathBuf> {
+    std::env::var(RELAY_LIST_FILE_ENV).ok().map(PathBuf::from).or(config_path)
 }
 
 fn default_pbs() -> String {
@@ -108,17 +568,14 @@ fn default_pbs() -> String {
 pub fn load_pbs_config() -> Result<PbsModuleConfig> {
     let config = CommitBoostConfig::from_env_path()?;
 
-    let relay_clients =
-        config.relays.into_iter().map(RelayClient::new).collect::<Result<Vec<_>>>()?;
-    let maybe_publiher = BuilderEventPublisher::new_from_env()?;
-
-    Ok(PbsModuleConfig {
-        chain: config.chain,
-        pbs_config: Arc::new(config.pbs.pbs_config),
-        relays: relay_clients,
-        signer_client: None,
-        event_publiher: maybe_publiher,
-    })
+    build_pbs_module_config(
+        config.chain,
+        config.relays,
+        config.pbs.pbs_config,
+        config.enable_ipv6,
+        config.feature_flags,
+        config.pbs_global,
+    )
 }
 
 /// Loads a custom pbs config, i.e. with signer client and/or custom data
@@ -136,15 +593,21 @@ pub fn load_pbs_custom_config<T: DeserializeOwned>() -> Result<(PbsModuleConfig,
         chain: Chain,
         relays: Vec<RelayConfig>,
         pbs: CustomPbsConfig<U>,
+        #[serde(default = "default_bool::<false>")]
+        enable_ipv6: bool,
+        #[serde(default)]
+        feature_flags: FeatureFlags,
+        #[serde(flatten)]
+        pbs_global: PbsGlobalConfig,
     }
 
     // load module config including the extra data (if any)
     let cb_config: StubConfig<T> = load_file_from_env(CONFIG_ENV)?;
-    cb_config.pbs.static_config.pbs_config.validate()?;
-
-    let relay_clients =
-        cb_config.relays.into_iter().map(RelayClient::new).collect::<Result<Vec<_>>>()?;
-    let maybe_publiher = BuilderEventPublisher::new_from_env()?;
+    cb_config.pbs.static_config.pbs_config.validate(
+        cb_config.chain,
+        &cb_config.pbs_global,
+        &cb_config.relays,
+    )?;
 
     let signer_client = if cb_config.pbs.static_config.with_signer {
         // if custom pbs requires a signer client, load jwt
@@ -155,14 +618,187 @@ pub fn load_pbs_custom_config<T: DeserializeOwned>() -> Result<(PbsModuleConfig,
         None
     };
 
-    Ok((
-        PbsModuleConfig {
-            chain: cb_config.chain,
-            pbs_config: Arc::new(cb_config.pbs.static_config.pbs_config),
-            relays: relay_clients,
-            signer_client,
-            event_publiher: maybe_publiher,
-        },
-        cb_config.pbs.extra,
-    ))
+    let pbs_module_config = build_pbs_module_config(
+        cb_config.chain,
+        cb_config.relays,
+        cb_config.pbs.static_config.pbs_config,
+        cb_config.enable_ipv6,
+        cb_config.feature_flags,
+        cb_config.pbs_global,
+    )?;
+
+    Ok((PbsModuleConfig { signer_client, ..pbs_module_config }, cb_config.pbs.extra))
+}
+
+/// Builds the runtime config from a validated config file, without a signer
+/// client
+fn build_pbs_module_config(
+    chain: Chain,
+    relays: Vec<RelayConfig>,
+    pbs_config: PbsConfig,
+    enable_ipv6: bool,
+    feature_flags: FeatureFlags,
+    mut pbs_global: PbsGlobalConfig,
+) -> Result<PbsModuleConfig> {
+    let require_https = pbs_config.is_https_required(chain);
+    let relay_clients = load_relay_clients(
+        dedup_relays(relays),
+        &pbs_global.relay_set_name,
+        pbs_config.relay_timeout_ms,
+        &pbs_config.client,
+        require_https,
+        pbs_global.is_plaintext_on_loopback_allowed(chain),
+    )?;
+    let maybe_publiher = BuilderEventPublisher::new_from_env()?;
+    let beacon_timeout = Duration::from_millis(pbs_global.beacon_client_timeout_ms);
+    let beacon_client = pbs_config
+        .consensus_client_endpoint
+        .clone()
+        .map(|url| BeaconApiClient::new(url, beacon_timeout))
+        .transpose()?;
+    let execution_client =
+        pbs_config.execution_client_endpoint.clone().map(load_execution_client).transpose()?;
+    let keymanager_client =
+        pbs_global.keymanager_api_endpoint.clone().map(load_keymanager_client).transpose()?;
+    let events_api_jwt = pbs_global
+        .enable_events_api
+        .then(|| load_env_var(EVENTS_API_JWT_ENV).map(Jwt))
+        .transpose()?;
+    pbs_global.relay_list_file = relay_list_file_path(pbs_global.relay_list_file);
+
+    Ok(PbsModuleConfig {
+        chain,
+        pbs_config: Arc::new(pbs_config),
+        relays: relay_clients,
+        signer_client: None,
+        event_publiher: maybe_publiher,
+        enable_ipv6,
+        pbs_global,
+        beacon_client,
+        execution_client,
+        feature_flags,
+        uptime_start: SystemTime::now(),
+        keymanager_client,
+        events_api_jwt,
+    })
+}
+
+/// Builds the relay clients, failing if https is required and a relay url
+/// doesn't use it. Loopback urls are exempt if `allow_plaintext_on_loopback`
+fn load_relay_clients(
+    relays: Vec<RelayConfig>,
+    relay_set_name: &Option<String>,
+    relay_timeout_ms: u64,
+    client_config: &PbsClientConfig,
+    require_https: bool,
+    allow_plaintext_on_loopback: bool,
+) -> Result<Vec<RelayClient>> {
+    relays
+        .into_iter()
+        .map(|mut relay| {
+            relay.request_timeout_ms.get_or_insert(relay_timeout_ms);
+
+            let url = &relay.entry.url;
+            let exempt = allow_plaintext_on_loopback && is_loopback(url);
+            if require_https && !exempt && url.scheme() != "https" {
+                return Err(PbsError::HttpsRequired { relay_url: relay.entry.url }.into());
+            }
+
+            Ok(RelayClient::new_with_client_config(relay, client_config)?
+                .with_relay_set(relay_set_name.clone()))
+        })
+        .collect()
+}
+
+/// Fails if more than one relay is configured with the same url
+pub fn check_duplicate_relays(relays: &[RelayConfig]) -> Result<()> {
+    let mut urls = HashSet::new();
+    for relay in relays {
+        ensure!(urls.insert(&relay.entry.url), "duplicate relay url: {}", relay.entry.url);
+    }
+
+    Ok(())
+}
+
+/// Keeps only the first relay configured for each url, logging a warning for
+/// the duplicates
+fn dedup_relays(relays: Vec<RelayConfig>) -> Vec<RelayConfig> {
+    let mut urls = HashSet::new();
+    relays
+        .into_iter()
+        .filter(|relay| {
+            let unique = urls.insert(relay.entry.url.clone());
+            if !unique {
+                warn!(url = %relay.entry.url, "duplicate relay url, skipping");
+            }
+            unique
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::{
+        check_duplicate_relays, dedup_relays, load_relay_clients, PbsClientConfig, RelayConfig,
+        RelayRetryPolicy,
+    };
+
+    #[test]
+    fn test_allow_plaintext_on_loopback() {
+        let client_config = PbsClientConfig::default();
+        let load = |relay: RelayConfig, require_https, allow_plaintext_on_loopback| {
+            load_relay_clients(
+                vec![relay],
+                &None,
+                1000,
+                &client_config,
+                require_https,
+                allow_plaintext_on_loopback,
+            )
+        };
+
+        let relay = |host: &str| -> RelayConfig {
+            let url = format!("http://0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae@{host}");
+            serde_json::from_value(json!({ "url": url })).unwrap()
+        };
+
+        for host in ["127.0.0.1:18550", "localhost:18550", "[::1]:18550"] {
+            assert!(load(relay(host), true, true).is_ok());
+            assert!(load(relay(host), true, false).is_err());
+        }
+
+        assert!(load(relay("abc.xyz"), true, true).is_err());
+        assert!(load(relay("abc.xyz"), false, false).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_relays() {
+        let relay = |id: &str| -> RelayConfig {
+            let url = "http://0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae@abc.xyz";
+            serde_json::from_value(json!({ "id": id, "url": url })).unwrap()
+        };
+
+        assert!(check_duplicate_relays(&[relay("a")]).is_ok());
+        assert!(check_duplicate_relays(&[relay("a"), relay("b")]).is_err());
+
+        let relays = dedup_relays(vec![relay("a"), relay("b")]);
+        assert_eq!(relays.len(), 1);
+        assert_eq!(relays[0].id.as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let policy =
+            RelayRetryPolicy { max_retries: 10, initial_backoff_ms: 100, max_backoff_ms: 1000 };
+
+        for (retry, max_ms) in [(1, 100), (2, 200), (3, 400), (4, 800), (5, 1000), (64, 1000)] {
+            let backoff = policy.backoff(retry);
+            assert!(backoff <= Duration::from_millis(max_ms));
+            assert!(backoff >= Duration::from_millis(max_ms / 2));
+        }
+    }
 }
@@ -1,11 +1,13 @@
+use std::path::PathBuf;
+
 use bimap::BiHashMap;
 use eyre::Result;
 use serde::{Deserialize, Serialize};
 
 use super::{
     constants::SIGNER_IMAGE_DEFAULT,
-    utils::{load_env_var, load_jwts},
-    CommitBoostConfig, SIGNER_PORT_ENV,
+    utils::{load_env_var, load_jwts, load_optional_env_var},
+    CommitBoostConfig, SIGNER_AUDIT_LOG_ENV, SIGNER_PORT_ENV,
 };
 use crate::{
     loader::SignerLoader,
@@ -19,6 +21,8 @@ pub struct SignerConfig {
     pub docker_image: String,
     /// Which keys to load
     pub loader: SignerLoader,
+    /// If set, every signing event is appended as a JSON line to this file
+    pub audit_log_path: Option<PathBuf>,
 }
 
 fn default_signer() -> String {
@@ -31,6 +35,9 @@ pub struct StartSignerConfig {
     pub loader: SignerLoader,
     pub server_port: u16,
     pub jwts: BiHashMap<ModuleId, Jwt>,
+    pub enable_ipv6: bool,
+    pub module_api_version: String,
+    pub audit_log_path: Option<PathBuf>,
 }
 
 impl StartSignerConfig {
@@ -39,12 +46,20 @@ impl StartSignerConfig {
 
         let jwts = load_jwts()?;
         let server_port = load_env_var(SIGNER_PORT_ENV)?.parse()?;
+        let signer = config.signer.expect("Signer config is missing");
+        // if running in docker, the audit log is mounted at a different path
+        let audit_log_path = load_optional_env_var(SIGNER_AUDIT_LOG_ENV)
+            .map(PathBuf::from)
+            .or(signer.audit_log_path);
 
         Ok(StartSignerConfig {
             chain: config.chain,
-            loader: config.signer.expect("Signer config is missing").loader,
+            loader: signer.loader,
             server_port,
             jwts,
+            enable_ipv6: config.enable_ipv6,
+            module_api_version: config.module_api_version,
+            audit_log_path,
         })
     }
 }
@@ -1,9 +1,10 @@
 use bimap::BiHashMap;
 use eyre::{bail, Context, Ok, Result};
 use serde::de::DeserializeOwned;
+use tracing::warn;
 
-use super::constants::JWTS_ENV;
-use crate::types::{Jwt, ModuleId};
+use super::constants::{CHAIN_ENV, JWTS_ENV};
+use crate::types::{Chain, Jwt, ModuleId};
 
 pub fn load_env_var(env: &str) -> Result<String> {
     std::env::var(env).wrap_err(format!("{env} is not set"))
@@ -18,9 +19,68 @@ pub fn load_from_file<T: DeserializeOwned>(path: &str) -> Result<T> {
     toml::from_str(&config_file).wrap_err("could not deserialize toml from string")
 }
 
+/// Loads the main config file at `path`. If `chain` is missing from the file,
+/// it's read from the `CB_CHAIN` env var
+pub fn load_config_from_file<T: DeserializeOwned>(path: &str) -> Result<T> {
+    let config_file =
+        std::fs::read_to_string(path).wrap_err(format!("Unable to find config file: {path}"))?;
+    let mut config: toml::Table =
+        toml::from_str(&config_file).wrap_err("could not deserialize toml from string")?;
+    set_chain_fallback(&mut config, load_optional_env_var(CHAIN_ENV))?;
+    toml::Value::Table(config).try_into().wrap_err("could not deserialize toml from string")
+}
+
+/// Parses the main config from YAML, with the same `CB_CHAIN` fallback as
+/// [load_config_from_file]
+pub fn load_config_from_yaml<T: DeserializeOwned>(yaml_str: &str) -> Result<T> {
+    let mut config: serde_yaml::Mapping =
+        serde_yaml::from_str(yaml_str).wrap_err("could not decode yaml config")?;
+    set_yaml_chain_fallback(&mut config, load_optional_env_var(CHAIN_ENV))?;
+    serde_yaml::from_value(serde_yaml::Value::Mapping(config))
+        .wrap_err("could not decode yaml config")
+}
+
+/// Loads the main config file from the path in `env`, see
+/// [load_config_from_file]
 pub fn load_file_from_env<T: DeserializeOwned>(env: &str) -> Result<T> {
     let path = std::env::var(env).wrap_err(format!("{env} is not set"))?;
-    load_from_file(&path)
+    load_config_from_file(&path)
+}
+
+/// Sets `chain` to `env_chain` if it's missing from the config. If both are
+/// set, the config takes precedence
+fn set_chain_fallback(config: &mut toml::Table, env_chain: Option<String>) -> Result<()> {
+    if let Some(chain) = chain_fallback(config.contains_key("chain"), env_chain)? {
+        config.insert("chain".to_string(), toml::Value::try_from(chain)?);
+    }
+    Ok(())
+}
+
+/// Same as [set_chain_fallback], for YAML configs
+fn set_yaml_chain_fallback(
+    config: &mut serde_yaml::Mapping,
+    env_chain: Option<String>,
+) -> Result<()> {
+    if let Some(chain) = chain_fallback(config.contains_key("chain"), env_chain)? {
+        config.insert("chain".into(), serde_yaml::to_value(chain)?);
+    }
+    Ok(())
+}
+
+/// Chain to insert into a config, if `env_chain` is set and the config has no
+/// chain
+fn chain_fallback(has_chain: bool, env_chain: Option<String>) -> Result<Option<Chain>> {
+    let Some(env_chain) = env_chain else {
+        return Ok(None);
+    };
+
+    if has_chain {
+        warn!("both chain and {CHAIN_ENV} are set, using the chain from the config");
+        return Ok(None);
+    }
+
+    let chain = env_chain.parse().wrap_err(format!("invalid {CHAIN_ENV}"))?;
+    Ok(Some(chain))
 }
 
 /// Loads a bidirectional map of module id <-> jwt token from a json env
@@ -56,4 +116,29 @@ mod tests {
         assert_eq!(map.get_by_left(&ModuleId("KEY".into())), Some(&Jwt("VALUE".into())));
         assert_eq!(map.get_by_left(&ModuleId("KEY2".into())), Some(&Jwt("value2".into())));
     }
+
+    #[test]
+    fn test_set_chain_fallback() {
+        #[derive(serde::Deserialize)]
+        struct MockConfig {
+            chain: Chain,
+        }
+
+        let mut config = toml::Table::new();
+        set_chain_fallback(&mut config, Some("holesky".to_string())).unwrap();
+        let decoded: MockConfig = toml::Value::Table(config).try_into().unwrap();
+        assert_eq!(decoded.chain, Chain::Holesky);
+
+        // the config takes precedence
+        let mut config: toml::Table = toml::from_str(r#"chain = "Mainnet""#).unwrap();
+        set_chain_fallback(&mut config, Some("holesky".to_string())).unwrap();
+        let decoded: MockConfig = toml::Value::Table(config).try_into().unwrap();
+        assert_eq!(decoded.chain, Chain::Mainnet);
+
+        let mut config = toml::Table::new();
+        assert!(set_chain_fallback(&mut config, Some("unknown".to_string())).is_err());
+
+        set_chain_fallback(&mut config, None).unwrap();
+        assert!(toml::Value::Table(config).try_into::<MockConfig>().is_err());
+    }
 }
@@ -1,3 +1,6 @@
 pub const APPLICATION_BUILDER_DOMAIN: [u8; 4] = [0, 0, 0, 1];
+/// Set in all application domains, e.g. builder and commit-boost signatures
+pub const DOMAIN_APPLICATION_MASK: [u8; 4] = [0, 0, 0, 1];
 pub const GENESIS_VALIDATORS_ROOT: [u8; 32] = [0; 32];
 pub const COMMIT_BOOST_DOMAIN: [u8; 4] = [109, 109, 111, 67];
+pub const SLOTS_PER_EPOCH: u64 = 32;
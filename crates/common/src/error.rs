@@ -28,6 +28,34 @@ impl Display for BlstErrorWrapper {
         }
     }
 }
+
+impl BlstErrorWrapper {
+    /// A key or signature could not be decoded into a valid point, e.g. a
+    /// malformed relay pubkey
+    pub fn is_invalid_pubkey(&self) -> bool {
+        matches!(
+            self,
+            BlstErrorWrapper::BlstBadEncoding(_) |
+                BlstErrorWrapper::BlstPointNotOnCurve(_) |
+                BlstErrorWrapper::BlstPointNotInGroup(_) |
+                BlstErrorWrapper::BlstPkIsInfinity(_)
+        )
+    }
+
+    /// The signature was well formed but didn't verify
+    pub fn is_invalid_signature(&self) -> bool {
+        matches!(self, BlstErrorWrapper::BlstVerifyFail(_))
+    }
+
+    pub fn is_wrong_encoding(&self) -> bool {
+        matches!(self, BlstErrorWrapper::BlstBadEncoding(_))
+    }
+
+    pub fn is_point_not_on_curve(&self) -> bool {
+        matches!(self, BlstErrorWrapper::BlstPointNotOnCurve(_))
+    }
+}
+
 impl From<BLST_ERROR> for BlstErrorWrapper {
     fn from(value: BLST_ERROR) -> Self {
         match value {
@@ -56,3 +84,26 @@ impl From<BLST_ERROR> for BlstErrorWrapper {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use blst::BLST_ERROR;
+
+    use super::BlstErrorWrapper;
+
+    #[test]
+    fn test_predicates() {
+        let err = BlstErrorWrapper::from(BLST_ERROR::BLST_VERIFY_FAIL);
+        assert!(err.is_invalid_signature());
+        assert!(!err.is_invalid_pubkey());
+
+        let err = BlstErrorWrapper::from(BLST_ERROR::BLST_BAD_ENCODING);
+        assert!(err.is_wrong_encoding());
+        assert!(err.is_invalid_pubkey());
+        assert!(!err.is_invalid_signature());
+
+        let err = BlstErrorWrapper::from(BLST_ERROR::BLST_POINT_NOT_ON_CURVE);
+        assert!(err.is_point_not_on_curve());
+        assert!(err.is_invalid_pubkey());
+    }
+}
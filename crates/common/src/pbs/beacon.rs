@@ -0,0 +1,243 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use alloy::{primitives::B256, rpc::types::beacon::BlsPublicKey};
+use eyre::{bail, Result};
+use reqwest::RequestBuilder;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use url::Url;
+
+const GENESIS_PATH: &str = "/eth/v1/beacon/genesis";
+const PROPOSER_DUTIES_PATH: &str = "/eth/v1/validator/duties/proposer";
+const SYNCING_PATH: &str = "/eth/v1/node/syncing";
+const VALIDATORS_PATH: &str = "/eth/v1/beacon/states/head/validators";
+
+/// Response envelope used by the beacon API
+#[derive(Debug, Deserialize)]
+pub struct BeaconApiResponse<T> {
+    pub data: T,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenesisInfo {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    #[serde(with = "alloy::hex::serde")]
+    pub genesis_fork_version: [u8; 4],
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProposerDuty {
+    pub pubkey: BlsPublicKey,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyncStatus {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub head_slot: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub sync_distance: u64,
+    pub is_syncing: bool,
+    #[serde(default)]
+    pub is_optimistic: bool,
+    #[serde(default)]
+    pub el_offline: bool,
+}
+
+/// Simplified validator status, grouping the statuses returned by the beacon
+/// API, see https://hackmd.io/ofFJ5gOmQpu1jjHilHbdQQ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum ValidatorStatus {
+    Pending,
+    Active,
+    Exited,
+    Slashed,
+}
+
+impl TryFrom<String> for ValidatorStatus {
+    type Error = String;
+
+    fn try_from(status: String) -> Result<Self, Self::Error> {
+        match status.as_str() {
+            "pending_initialized" | "pending_queued" => Ok(Self::Pending),
+            "active_ongoing" | "active_exiting" => Ok(Self::Active),
+            "active_slashed" | "exited_slashed" => Ok(Self::Slashed),
+            "exited_unslashed" | "withdrawal_possible" | "withdrawal_done" => Ok(Self::Exited),
+            other => Err(format!("unknown validator status: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ValidatorInfo {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    pub status: ValidatorStatus,
+    pub validator: ValidatorData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ValidatorData {
+    pub pubkey: BlsPublicKey,
+}
+
+/// A client to query the beacon API of a consensus client
+#[derive(Debug, Clone)]
+pub struct BeaconApiClient {
+    /// Url endpoint of the consensus client
+    url: Arc<Url>,
+    client: reqwest::Client,
+}
+
+impl BeaconApiClient {
+    pub fn new(url: Url, timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(timeout).build()?;
+        Ok(Self { url: url.into(), client })
+    }
+
+    /// Implements https://ethereum.github.io/beacon-APIs/#/Beacon/getGenesis
+    pub async fn get_genesis(&self) -> Result<GenesisInfo> {
+        self.get(GENESIS_PATH).await
+    }
+
+    /// Implements https://ethereum.github.io/beacon-APIs/#/Validator/getProposerDuties
+    pub async fn get_proposer_duties(&self, epoch: u64) -> Result<Vec<ProposerDuty>> {
+        self.get(&format!("{PROPOSER_DUTIES_PATH}/{epoch}")).await
+    }
+
+    /// Implements https://ethereum.github.io/beacon-APIs/#/Node/getSyncingStatus
+    pub async fn get_sync_status(&self) -> Result<SyncStatus> {
+        self.get(SYNCING_PATH).await
+    }
+
+    /// Returns the status of the given validators at the head state.
+    /// Validators unknown to the consensus client, e.g. with a deposit that
+    /// hasn't been processed yet, are not included.
+    /// Implements https://ethereum.github.io/beacon-APIs/#/Beacon/postStateValidators
+    pub async fn get_validator_statuses(
+        &self,
+        pubkeys: &[BlsPublicKey],
+    ) -> Result<HashMap<BlsPublicKey, ValidatorStatus>> {
+        #[derive(Serialize)]
+        struct ValidatorsRequest<'a> {
+            ids: &'a [BlsPublicKey],
+        }
+
+        let url = self.url.join(VALIDATORS_PATH)?;
+        let validators: Vec<ValidatorInfo> =
+            self.send(self.client.post(url).json(&ValidatorsRequest { ids: pubkeys })).await?;
+
+        Ok(validators.into_iter().map(|info| (info.validator.pubkey, info.status)).collect())
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = self.url.join(path)?;
+        self.send(self.client.get(url)).await
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let res = request.send().await?;
+
+        let status = res.status();
+        let response_bytes = res.bytes().await?;
+
+        if !status.is_success() {
+            bail!(
+                "beacon API request failed: status {status}; message: \"{}\"",
+                String::from_utf8_lossy(&response_bytes)
+            );
+        }
+
+        let response: BeaconApiResponse<T> = serde_json::from_slice(&response_bytes)?;
+        Ok(response.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_proposer_duties() {
+        let data = r#"{
+            "dependent_root": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+            "execution_optimistic": false,
+            "data": [
+                {
+                    "pubkey": "0x93247f2209abcacf57b75a51dafae777f9dd38bc7053d1af526f220a7489a6d3a2753e5f3e8b1cfe39b56f43611df74a",
+                    "validator_index": "1",
+                    "slot": "32"
+                }
+            ]
+        }"#;
+
+        let duties: BeaconApiResponse<Vec<ProposerDuty>> = serde_json::from_str(data).unwrap();
+        assert_eq!(duties.data.len(), 1);
+        assert_eq!(duties.data[0].validator_index, 1);
+        assert_eq!(duties.data[0].slot, 32);
+    }
+
+    #[test]
+    fn test_decode_sync_status() {
+        let data = r#"{
+            "data": {
+                "head_slot": "100",
+                "sync_distance": "2",
+                "is_syncing": true,
+                "is_optimistic": false
+            }
+        }"#;
+
+        let status: BeaconApiResponse<SyncStatus> = serde_json::from_str(data).unwrap();
+        assert_eq!(status.data.head_slot, 100);
+        assert_eq!(status.data.sync_distance, 2);
+        assert!(status.data.is_syncing);
+        assert!(!status.data.el_offline);
+    }
+
+    #[test]
+    fn test_decode_validators() {
+        let data = r#"{
+            "execution_optimistic": false,
+            "finalized": false,
+            "data": [
+                {
+                    "index": "1",
+                    "balance": "32000000000",
+                    "status": "active_ongoing",
+                    "validator": {
+                        "pubkey": "0x93247f2209abcacf57b75a51dafae777f9dd38bc7053d1af526f220a7489a6d3a2753e5f3e8b1cfe39b56f43611df74a",
+                        "withdrawal_credentials": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+                        "effective_balance": "32000000000",
+                        "slashed": false,
+                        "activation_eligibility_epoch": "0",
+                        "activation_epoch": "0",
+                        "exit_epoch": "18446744073709551615",
+                        "withdrawable_epoch": "18446744073709551615"
+                    }
+                }
+            ]
+        }"#;
+
+        let validators: BeaconApiResponse<Vec<ValidatorInfo>> = serde_json::from_str(data).unwrap();
+        assert_eq!(validators.data[0].index, 1);
+        assert_eq!(validators.data[0].status, ValidatorStatus::Active);
+
+        for (status, expected) in [
+            ("pending_queued", ValidatorStatus::Pending),
+            ("active_exiting", ValidatorStatus::Active),
+            ("active_slashed", ValidatorStatus::Slashed),
+            ("withdrawal_done", ValidatorStatus::Exited),
+        ] {
+            let status: ValidatorStatus = serde_json::from_str(&format!("\"{status}\"")).unwrap();
+            assert_eq!(status, expected);
+        }
+
+        assert!(serde_json::from_str::<ValidatorStatus>("\"unknown\"").is_err());
+    }
+}
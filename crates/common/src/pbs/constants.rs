@@ -8,11 +8,15 @@ pub const SUBMIT_BLOCK_PATH: &str = "/blinded_blocks";
 // https://ethereum.github.io/builder-specs/#/Builder
 
 pub const HEADER_SLOT_UUID_KEY: &str = "X-MEVBoost-SlotID";
+pub const HEADER_CONSENSUS_VERSION: &str = "Eth-Consensus-Version";
 pub const HEADER_VERSION_KEY: &str = "X-CommitBoost-Version";
 pub const HEADER_VERSION_VALUE: &str = env!("CARGO_PKG_VERSION");
 pub const HEADER_START_TIME_UNIX_MS: &str = "X-MEVBoost-StartTimeUnixMS";
 
 pub const BUILDER_EVENTS_PATH: &str = "/builder_events";
+pub const HEALTH_PATH: &str = "/admin/health";
+pub const STATS_PATH: &str = "/admin/stats";
+pub const EVENTS_PATH: &str = "/events";
 pub const DEFAULT_PBS_JWT_KEY: &str = "DEFAULT_PBS";
 
 #[non_exhaustive]
@@ -21,6 +25,21 @@ impl DefaultTimeout {
     pub const GET_HEADER_MS: u64 = 950;
     pub const GET_PAYLOAD_MS: u64 = 4000;
     pub const REGISTER_VALIDATOR_MS: u64 = 3000;
+    pub const RELAY_CONNECT_MS: u64 = 12_000;
 }
 
+pub const MIN_RELAY_TIMEOUT_MS: u64 = 50;
+
 pub const LATE_IN_SLOT_TIME_MS: u64 = 2000;
+pub const MAX_SLOT_LOOKAHEAD: u8 = 2;
+pub const RELAY_EXCLUSION_LIST_REFRESH_SECS: u64 = 60 * 60;
+pub const HEARTBEAT_INTERVAL_SECS: u64 = 60;
+pub const KEYMANAGER_REFRESH_SECS: u64 = 60;
+pub const BEACON_CLIENT_TIMEOUT_MS: u64 = 500;
+pub const SIMULATE_BID_VALUE_GWEI: u64 = 1_000_000;
+
+// https://eips.ethereum.org/EIPS/eip-4844#parameters
+pub const GAS_PER_BLOB: u64 = 1 << 17;
+pub const TARGET_BLOB_GAS_PER_BLOCK: u64 = 3 * GAS_PER_BLOB;
+// TODO: make this configurable, Electra raises the limit
+pub const MAX_BLOBS_PER_BLOCK: usize = 6;
@@ -2,9 +2,13 @@ use alloy::{
     primitives::{B256, U256},
     rpc::types::beacon::BlsPublicKey,
 };
+use axum::http::StatusCode;
+use serde::{ser::SerializeStruct, Serialize};
 use thiserror::Error;
+use url::Url;
 
-use crate::error::BlstErrorWrapper;
+use super::ValidatorStatus;
+use crate::{error::BlstErrorWrapper, types::Chain, utils::wei_to_eth};
 
 #[derive(Debug, Error)]
 pub enum PbsError {
@@ -20,20 +24,144 @@ pub enum PbsError {
     #[error("relay response error. Code: {code}, err: {error_msg}")]
     RelayResponse { error_msg: String, code: u16 },
 
-    #[error("Response size exceeds 10MB! Got: {payload_size}")]
+    #[error("Response size exceeds the max payload size! Got: {payload_size}")]
     PayloadTooLarge { payload_size: usize },
 
+    #[error("relay response is not json, content type: {got}")]
+    InvalidContentType { got: String },
+
+    #[error("invalid json content at {field}: {detail}")]
+    InvalidJsonContent { field: String, detail: String },
+
     #[error("failed validating relay response: {0}")]
     Validation(#[from] ValidationError),
 
+    #[error("failed signature verification: {0}")]
+    InvalidSignature(#[from] BlstErrorWrapper),
+
+    /// A proof from a relay failed verification, e.g. a Merkle proof. The
+    /// message should include the proof type and the reason
+    #[error("proof verification failed: {0}")]
+    ProofVerificationError(String),
+
     #[error("URL parsing error: {0}")]
     UrlParsing(#[from] url::ParseError),
+
+    #[error("a block was already submitted for slot {slot}")]
+    BlockAlreadySubmitted { slot: u64 },
+
+    #[error("chain mismatch: configured {configured:?}, consensus client is on {detected:?}")]
+    ChainMismatch { configured: Chain, detected: Chain },
+
+    #[error("validator {pubkey} is not active, status: {status:?}")]
+    ValidatorNotActive { pubkey: BlsPublicKey, status: ValidatorStatus },
+
+    #[error("validator {pubkey} is not managed by this node")]
+    ValidatorNotManaged { pubkey: BlsPublicKey },
+
+    #[error("slot {slot} is in the past, current slot is {current_slot}")]
+    SlotInPast { slot: u64, current_slot: u64 },
+
+    #[error("slot {slot} is more than {max_ahead} slots ahead of the current slot")]
+    SlotTooFarInFuture { slot: u64, max_ahead: u64 },
+
+    #[error("relay {relay_url} must use https")]
+    HttpsRequired { relay_url: Url },
+
+    /// The relay responded with a consensus version that is not supported,
+    /// e.g. after a fork this version of commit-boost doesn't support yet
+    #[error(
+        "version negotiation failed: relay uses {relay_version}, expected {our_version}, {}",
+        upgrade_hint(.our_version, .relay_version)
+    )]
+    VersionNegotiationFailed { our_version: String, relay_version: String },
+
+    /// Reloading the config on SIGHUP failed, the previous config is kept.
+    /// The message should include the reason, e.g. a syntax error
+    #[error("config reload failed: {0}")]
+    ConfigReloadFailed(String),
 }
 
 impl PbsError {
     pub fn is_timeout(&self) -> bool {
         matches!(self, PbsError::Reqwest(err) if err.is_timeout())
     }
+
+    /// Whether the relay timed out or failed with a 5xx, as opposed to
+    /// rejecting the request or returning an invalid response
+    pub fn is_relay_down(&self) -> bool {
+        self.is_timeout() || matches!(self, PbsError::RelayResponse { code, .. } if *code >= 500)
+    }
+
+    /// Whether a signature failed verification, either directly or while
+    /// validating a relay response
+    pub fn is_sig_error(&self) -> bool {
+        matches!(
+            self,
+            PbsError::InvalidSignature(_) | PbsError::Validation(ValidationError::Sigverify(_))
+        )
+    }
+
+    /// Numeric value of [PbsError::into_http_status]
+    pub fn status_code(&self) -> u16 {
+        self.into_http_status().as_u16()
+    }
+
+    /// Status code to return to the beacon node when this error is the reason
+    /// a request failed
+    pub fn into_http_status(&self) -> StatusCode {
+        match self {
+            PbsError::AxumError(_) |
+            PbsError::UrlParsing(_) |
+            PbsError::ChainMismatch { .. } |
+            PbsError::HttpsRequired { .. } |
+            PbsError::ConfigReloadFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            PbsError::Reqwest(err) if err.is_timeout() => StatusCode::GATEWAY_TIMEOUT,
+            PbsError::Validation(err) if err.is_request_error() => StatusCode::BAD_REQUEST,
+            PbsError::Reqwest(_) |
+            PbsError::SerdeDecodeError(_) |
+            PbsError::Validation(_) |
+            PbsError::PayloadTooLarge { .. } |
+            PbsError::InvalidContentType { .. } |
+            PbsError::InvalidJsonContent { .. } |
+            PbsError::InvalidSignature(_) |
+            PbsError::ProofVerificationError(_) |
+            PbsError::ValidatorNotActive { .. } |
+            PbsError::VersionNegotiationFailed { .. } => StatusCode::BAD_GATEWAY,
+            PbsError::RelayResponse { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            PbsError::ValidatorNotManaged { .. } |
+            PbsError::SlotInPast { .. } |
+            PbsError::SlotTooFarInFuture { .. } => StatusCode::BAD_REQUEST,
+            PbsError::BlockAlreadySubmitted { .. } => StatusCode::CONFLICT,
+        }
+    }
+}
+
+/// Consensus forks in activation order
+const FORKS: [&str; 7] = ["phase0", "altair", "bellatrix", "capella", "deneb", "electra", "fulu"];
+
+/// Which side to upgrade to resolve a version mismatch. Unknown relay versions
+/// are assumed to be newer forks
+fn upgrade_hint(our_version: &str, relay_version: &str) -> &'static str {
+    let position = |version| FORKS.iter().position(|fork| *fork == version);
+    match (position(our_version), position(relay_version)) {
+        (Some(ours), Some(relay)) if relay < ours => "the relay needs to be upgraded",
+        _ => "commit-boost needs to be upgraded",
+    }
+}
+
+/// Serialized as a builder API error response, e.g.
+/// `{"code": 502, "message": "..."}`
+impl Serialize for PbsError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("PbsError", 2)?;
+        state.serialize_field("code", &self.status_code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
 }
 
 #[derive(Debug, Error, PartialEq, Eq)]
@@ -47,9 +175,15 @@ pub enum ValidationError {
     #[error("parent hash mismatch: expected {expected} got {got}")]
     ParentHashMismatch { expected: B256, got: B256 },
 
+    #[error("parent hash {parent_hash} not found by the execution client")]
+    ParentHashNotFound { parent_hash: B256 },
+
     #[error("block hash mismatch: expected {expected} got {got}")]
     BlockHashMismatch { expected: B256, got: B256 },
 
+    #[error("slot mismatch: expected {expected} got {got}")]
+    SlotMismatch { expected: u64, got: u64 },
+
     #[error("mismatch in KZG commitments: exepcted_blobs: {expected_blobs} got_blobs: {got_blobs} got_commitments: {got_commitments} got_proofs: {got_proofs}")]
     KzgCommitments {
         expected_blobs: usize,
@@ -61,12 +195,175 @@ pub enum ValidationError {
     #[error("mismatch in KZG blob commitment: expected: {expected} got: {got} index: {index}")]
     KzgMismatch { expected: String, got: String, index: usize },
 
-    #[error("bid below minimum: min: {min} got {got}")]
+    #[error("too many blobs: max: {max} got: {got}")]
+    LargeBlobCount { max: usize, got: usize },
+
+    #[error(
+        "bid below minimum: min: {min} ({} ETH) got {got} ({} ETH)",
+        wei_to_eth_str(*.min),
+        wei_to_eth_str(*.got)
+    )]
     BidTooLow { min: U256, got: U256 },
 
+    #[error("bid above relay maximum: max: {max} got {got}")]
+    ValueTooHigh { max: U256, got: U256 },
+
     #[error("empty tx root")]
     EmptyTxRoot,
 
+    #[error("gas used exceeds gas limit: gas_limit: {gas_limit} gas_used: {gas_used}")]
+    GasUsedExceedsGasLimit { gas_limit: u64, gas_used: u64 },
+
+    #[error("gas limit mismatch: registered: {registered} got: {got}")]
+    GasLimitMismatch { registered: u64, got: u64 },
+
+    #[error("excess blob gas out of range: expected {expected} got {got}")]
+    ExcessBlobGasOutOfRange { expected: U256, got: U256 },
+
     #[error("failed signature verification: {0:?}")]
     Sigverify(#[from] BlstErrorWrapper),
+
+    #[error("pubkey {pubkey} is not the expected proposer for slot {slot}")]
+    PubkeyNotInCommittee { pubkey: BlsPublicKey, slot: u64 },
+}
+
+impl ValidationError {
+    /// Whether the request of the beacon node failed validation, as opposed to
+    /// an invalid relay response
+    pub fn is_request_error(&self) -> bool {
+        matches!(
+            self,
+            ValidationError::ParentHashNotFound { .. } |
+                ValidationError::SlotMismatch { .. } |
+                ValidationError::PubkeyNotInCommittee { .. }
+        )
+    }
+}
+
+/// Formats a wei value as decimal ETH with 6 significant figures, e.g.
+/// "0.0123457"
+pub fn wei_to_eth_str(v: U256) -> String {
+    let eth = wei_to_eth(&v);
+    if eth == 0.0 {
+        return "0".to_string();
+    }
+
+    let magnitude = eth.log10().floor() as i32;
+    let decimals = (5 - magnitude).max(0) as usize;
+    format!("{eth:.decimals$}")
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::U256;
+    use axum::http::StatusCode;
+    use blst::BLST_ERROR;
+
+    use super::{wei_to_eth_str, BlstErrorWrapper, PbsError, ValidationError, ValidatorStatus};
+
+    #[test]
+    fn test_is_relay_down() {
+        let relay_error = |code| PbsError::RelayResponse { error_msg: String::new(), code };
+
+        assert!(relay_error(500).is_relay_down());
+        assert!(relay_error(503).is_relay_down());
+        assert!(!relay_error(400).is_relay_down());
+        assert!(!PbsError::Validation(ValidationError::EmptyBlockhash).is_relay_down());
+    }
+
+    #[test]
+    fn test_validation_error_status() {
+        let relay_err = PbsError::Validation(ValidationError::EmptyBlockhash);
+        assert!(!relay_err.is_relay_down());
+        assert_eq!(relay_err.into_http_status(), StatusCode::BAD_GATEWAY);
+
+        let request_err = PbsError::Validation(ValidationError::PubkeyNotInCommittee {
+            pubkey: Default::default(),
+            slot: 1,
+        });
+        assert_eq!(request_err.into_http_status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_validator_not_active_status() {
+        // the status comes from the consensus client, not from the request
+        let err = PbsError::ValidatorNotActive {
+            pubkey: Default::default(),
+            status: ValidatorStatus::Pending,
+        };
+        assert_eq!(err.into_http_status(), StatusCode::BAD_GATEWAY);
+    }
+
+    #[test]
+    fn test_proof_verification_error() {
+        let err = PbsError::ProofVerificationError("merkle proof: root mismatch".to_string());
+        assert_eq!(err.to_string(), "proof verification failed: merkle proof: root mismatch");
+        assert_eq!(err.into_http_status(), StatusCode::BAD_GATEWAY);
+        assert!(!err.is_relay_down());
+    }
+
+    #[test]
+    fn test_is_sig_error() {
+        let blst_err = || BlstErrorWrapper::from(BLST_ERROR::BLST_VERIFY_FAIL);
+
+        let err = PbsError::from(blst_err());
+        assert!(matches!(err, PbsError::InvalidSignature(_)));
+        assert!(err.is_sig_error());
+        assert_eq!(err.into_http_status(), StatusCode::BAD_GATEWAY);
+
+        assert!(PbsError::Validation(ValidationError::Sigverify(blst_err())).is_sig_error());
+        assert!(!PbsError::Validation(ValidationError::EmptyBlockhash).is_sig_error());
+    }
+
+    #[test]
+    fn test_version_negotiation_failed() {
+        let err = |relay_version: &str| PbsError::VersionNegotiationFailed {
+            our_version: "deneb".to_string(),
+            relay_version: relay_version.to_string(),
+        };
+
+        assert_eq!(
+            err("electra").to_string(),
+            "version negotiation failed: relay uses electra, expected deneb, commit-boost needs \
+             to be upgraded"
+        );
+        assert!(err("capella").to_string().ends_with("the relay needs to be upgraded"));
+        assert!(err("unknown").to_string().ends_with("commit-boost needs to be upgraded"));
+        assert_eq!(err("electra").into_http_status(), StatusCode::BAD_GATEWAY);
+    }
+
+    #[test]
+    fn test_config_reload_failed() {
+        let err = PbsError::ConfigReloadFailed("invalid relay url".to_string());
+        assert_eq!(err.to_string(), "config reload failed: invalid relay url");
+        assert_eq!(err.into_http_status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_serialize_pbs_error() {
+        let err = PbsError::SlotInPast { slot: 1, current_slot: 2 };
+        assert_eq!(err.status_code(), 400);
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({ "code": 400, "message": "slot 1 is in the past, current slot is 2" })
+        );
+    }
+
+    #[test]
+    fn test_wei_to_eth_str() {
+        assert_eq!(wei_to_eth_str(U256::ZERO), "0");
+        assert_eq!(wei_to_eth_str(U256::from(10u64.pow(18))), "1.00000");
+        assert_eq!(wei_to_eth_str(U256::from(12_345_678_000_000_000u64)), "0.0123457");
+        assert_eq!(wei_to_eth_str(U256::from(1_234_567u64) * U256::from(10u64.pow(18))), "1234567");
+
+        let err = ValidationError::BidTooLow {
+            min: U256::from(10u64.pow(17)),
+            got: U256::from(5 * 10u64.pow(16)),
+        };
+        assert_eq!(
+            err.to_string(),
+            "bid below minimum: min: 100000000000000000 (0.100000 ETH) got 50000000000000000 \
+             (0.0500000 ETH)"
+        );
+    }
 }
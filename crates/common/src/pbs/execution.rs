@@ -0,0 +1,304 @@
+use std::sync::Arc;
+
+use alloy::primitives::{Address, Bytes, B256, B64, U256, U64};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use eyre::{bail, eyre, Result};
+use hmac::{Hmac, Mac};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use ssz_types::{FixedVector, VariableList};
+use url::Url;
+
+use super::{DenebSpec, ExecutionPayload, Withdrawal};
+use crate::{utils::utcnow_sec, DEFAULT_REQUEST_TIMEOUT};
+
+/// Response envelope used by the JSON-RPC API
+#[derive(Debug, Deserialize)]
+pub struct JsonRpcResponse<T> {
+    pub result: Option<T>,
+    pub error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// Subset of the block fields returned by `eth_getBlockByHash`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionBlock {
+    pub hash: B256,
+    pub parent_hash: B256,
+    pub gas_limit: U64,
+    /// Only set for blocks after Cancun
+    pub excess_blob_gas: Option<U64>,
+    /// Only set for blocks after Cancun
+    pub blob_gas_used: Option<U64>,
+}
+
+/// `ExecutionPayloadV3` in the engine API format
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EngineExecutionPayload {
+    parent_hash: B256,
+    fee_recipient: Address,
+    state_root: B256,
+    receipts_root: B256,
+    logs_bloom: Bytes,
+    prev_randao: B256,
+    block_number: U64,
+    gas_limit: U64,
+    gas_used: U64,
+    timestamp: U64,
+    extra_data: Bytes,
+    base_fee_per_gas: U256,
+    block_hash: B256,
+    transactions: Vec<Bytes>,
+    withdrawals: Vec<EngineWithdrawal>,
+    blob_gas_used: U64,
+    excess_blob_gas: U64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EngineWithdrawal {
+    index: U64,
+    validator_index: U64,
+    address: Address,
+    amount: U64,
+}
+
+impl EngineExecutionPayload {
+    fn into_payload(self) -> Result<ExecutionPayload<DenebSpec>> {
+        let transactions = self
+            .transactions
+            .into_iter()
+            .map(|tx| VariableList::new(tx.to_vec()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| eyre!("invalid transaction: {err:?}"))?;
+        let withdrawals = self
+            .withdrawals
+            .into_iter()
+            .map(|withdrawal| Withdrawal {
+                index: withdrawal.index.to(),
+                validator_index: withdrawal.validator_index.to(),
+                address: withdrawal.address,
+                amount: withdrawal.amount.to(),
+            })
+            .collect();
+
+        Ok(ExecutionPayload {
+            parent_hash: self.parent_hash,
+            fee_recipient: self.fee_recipient,
+            state_root: self.state_root,
+            receipts_root: self.receipts_root,
+            logs_bloom: FixedVector::new(self.logs_bloom.to_vec())
+                .map_err(|err| eyre!("invalid logs bloom: {err:?}"))?,
+            prev_randao: self.prev_randao,
+            block_number: self.block_number.to(),
+            gas_limit: self.gas_limit.to(),
+            gas_used: self.gas_used.to(),
+            timestamp: self.timestamp.to(),
+            extra_data: VariableList::new(self.extra_data.to_vec())
+                .map_err(|err| eyre!("invalid extra data: {err:?}"))?,
+            base_fee_per_gas: self.base_fee_per_gas,
+            block_hash: self.block_hash,
+            transactions: VariableList::new(transactions)
+                .map_err(|err| eyre!("too many transactions: {err:?}"))?,
+            withdrawals: VariableList::new(withdrawals)
+                .map_err(|err| eyre!("too many withdrawals: {err:?}"))?,
+            blob_gas_used: self.blob_gas_used.to(),
+            excess_blob_gas: self.excess_blob_gas.to(),
+        })
+    }
+}
+
+/// A client to query the JSON-RPC API of an execution client
+#[derive(Debug, Clone)]
+pub struct ExecutionApiClient {
+    /// Url endpoint of the execution client
+    url: Arc<Url>,
+    client: reqwest::Client,
+    /// Secret shared with the execution client to authenticate with the
+    /// engine API, if set
+    jwt_secret: Option<B256>,
+}
+
+impl ExecutionApiClient {
+    pub fn new(url: Url) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(DEFAULT_REQUEST_TIMEOUT).build()?;
+        Ok(Self { url: url.into(), client, jwt_secret: None })
+    }
+
+    /// Authenticates every request with a JWT signed with `jwt_secret`, as
+    /// required by the engine API. The url must then point to the
+    /// authenticated port of the execution client, e.g. 8551
+    pub fn with_jwt_secret(self, jwt_secret: B256) -> Self {
+        Self { jwt_secret: Some(jwt_secret), ..self }
+    }
+
+    /// Implements `eth_getBlockByHash`, without the full transactions.
+    /// Returns None if the block is unknown to the execution client
+    pub async fn get_block_by_hash(&self, hash: B256) -> Result<Option<ExecutionBlock>> {
+        self.call("eth_getBlockByHash", json!([hash, false])).await
+    }
+
+    /// Implements `engine_getPayloadV3`, returning the payload built locally
+    /// by the execution client for `payload_id`. The payload id is returned by
+    /// `engine_forkchoiceUpdatedV3` to whoever requested the build, usually
+    /// the consensus client. Requires a JWT secret
+    pub async fn get_local_payload(&self, payload_id: B64) -> Result<ExecutionPayload<DenebSpec>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct GetPayloadV3Response {
+            execution_payload: EngineExecutionPayload,
+        }
+
+        if self.jwt_secret.is_none() {
+            bail!("the engine API requires a JWT secret");
+        }
+
+        let response: Option<GetPayloadV3Response> =
+            self.call("engine_getPayloadV3", json!([payload_id])).await?;
+        let Some(response) = response else {
+            bail!("no payload returned for payload id {payload_id}");
+        };
+
+        response.execution_payload.into_payload()
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<Option<T>> {
+        #[derive(Serialize)]
+        struct JsonRpcRequest<'a> {
+            jsonrpc: &'static str,
+            id: u64,
+            method: &'a str,
+            params: Value,
+        }
+
+        let request = JsonRpcRequest { jsonrpc: "2.0", id: 1, method, params };
+        let mut request = self.client.post(self.url.as_str()).json(&request);
+        if let Some(secret) = &self.jwt_secret {
+            request = request.bearer_auth(engine_api_jwt(secret, utcnow_sec()));
+        }
+        let res = request.send().await?;
+
+        let status = res.status();
+        let response_bytes = res.bytes().await?;
+
+        if !status.is_success() {
+            bail!(
+                "execution API request failed: status {status}; message: \"{}\"",
+                String::from_utf8_lossy(&response_bytes)
+            );
+        }
+
+        let response: JsonRpcResponse<T> = serde_json::from_slice(&response_bytes)?;
+        if let Some(JsonRpcError { code, message }) = response.error {
+            bail!("execution API request failed: code {code}; message: \"{message}\"");
+        }
+
+        Ok(response.result)
+    }
+}
+
+/// Signs an HS256 JWT issued at `iat` to authenticate with the engine API,
+/// see https://github.com/ethereum/execution-apis/blob/main/src/engine/authentication.md
+fn engine_api_jwt(secret: &B256, iat: u64) -> String {
+    // base64url of {"alg":"HS256","typ":"JWT"}
+    const HEADER: &str = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9";
+
+    let claims = URL_SAFE_NO_PAD.encode(format!("{{\"iat\":{iat}}}"));
+    let message = format!("{HEADER}.{claims}");
+
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_slice()).expect("hmac accepts any key size");
+    mac.update(message.as_bytes());
+    let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+
+    format!("{message}.{signature}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_block_by_hash() {
+        let data = r#"{
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "hash": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+                "parentHash": "0x0101010101010101010101010101010101010101010101010101010101010101",
+                "number": "0x10",
+                "gasLimit": "0x1c9c380",
+                "excessBlobGas": "0x60000",
+                "blobGasUsed": "0x20000"
+            }
+        }"#;
+
+        let block: JsonRpcResponse<ExecutionBlock> = serde_json::from_str(data).unwrap();
+        let block = block.result.unwrap();
+        assert_eq!(block.parent_hash, B256::repeat_byte(1));
+        assert_eq!(block.gas_limit, U64::from(30_000_000));
+        assert_eq!(block.excess_blob_gas, Some(U64::from(393_216)));
+        assert_eq!(block.blob_gas_used, Some(U64::from(131_072)));
+
+        let data = r#"{ "jsonrpc": "2.0", "id": 1, "result": null }"#;
+        let block: JsonRpcResponse<ExecutionBlock> = serde_json::from_str(data).unwrap();
+        assert!(block.result.is_none());
+        assert!(block.error.is_none());
+    }
+
+    #[test]
+    fn test_engine_api_jwt() {
+        let jwt = engine_api_jwt(&B256::repeat_byte(1), 1_700_000_000);
+        assert_eq!(
+            jwt,
+            "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJpYXQiOjE3MDAwMDAwMDB9.\
+             S3esM_43rSn57zYhtZ-FpifXBK_oKaYJ0Dx6M3iWn70"
+        );
+    }
+
+    #[test]
+    fn test_decode_engine_payload() {
+        let data = format!(
+            r#"{{
+                "parentHash": "0x0101010101010101010101010101010101010101010101010101010101010101",
+                "feeRecipient": "0x4770b19c113cbc07e220d51e6a9fbc4ed30fa51a",
+                "stateRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "receiptsRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "logsBloom": "0x{}",
+                "prevRandao": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "blockNumber": "0x10",
+                "gasLimit": "0x1c9c380",
+                "gasUsed": "0x5208",
+                "timestamp": "0x6553f100",
+                "extraData": "0x",
+                "baseFeePerGas": "0x7",
+                "blockHash": "0x0202020202020202020202020202020202020202020202020202020202020202",
+                "transactions": ["0x02f870"],
+                "withdrawals": [{{ "index": "0x1", "validatorIndex": "0x2", "address": "0x4770b19c113cbc07e220d51e6a9fbc4ed30fa51a", "amount": "0x3" }}],
+                "blobGasUsed": "0x20000",
+                "excessBlobGas": "0x0"
+            }}"#,
+            "00".repeat(256)
+        );
+
+        let payload: EngineExecutionPayload = serde_json::from_str(&data).unwrap();
+        let payload = payload.into_payload().unwrap();
+        assert_eq!(payload.parent_hash, B256::repeat_byte(1));
+        assert_eq!(payload.block_number, 16);
+        assert_eq!(payload.gas_limit, 30_000_000);
+        assert_eq!(payload.gas_used, 21_000);
+        assert_eq!(payload.base_fee_per_gas, U256::from(7));
+        assert_eq!(payload.transactions.len(), 1);
+        assert_eq!(payload.transactions[0].to_vec(), vec![0x02, 0xf8, 0x70]);
+        assert_eq!(payload.withdrawals[0].validator_index, 2);
+        assert_eq!(payload.blob_gas_used, 131_072);
+    }
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use alloy::rpc::types::beacon::BlsPublicKey;
+use eyre::{bail, Result};
+use serde::Deserialize;
+use url::Url;
+
+use crate::{pbs::BeaconApiResponse, DEFAULT_REQUEST_TIMEOUT};
+
+const KEYSTORES_PATH: &str = "/eth/v1/keystores";
+
+/// A keystore managed by the validator client
+#[derive(Debug, Clone, Deserialize)]
+pub struct Keystore {
+    pub validating_pubkey: BlsPublicKey,
+    pub derivation_path: Option<String>,
+    #[serde(default)]
+    pub readonly: bool,
+}
+
+/// A client to query the keymanager API of a validator client
+#[derive(Debug, Clone)]
+pub struct KeymanagerClient {
+    /// Url endpoint of the validator client
+    url: Arc<Url>,
+    client: reqwest::Client,
+    /// Bearer token to authenticate with the keymanager API, if any
+    token: Option<String>,
+}
+
+impl KeymanagerClient {
+    pub fn new(url: Url, token: Option<String>) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(DEFAULT_REQUEST_TIMEOUT).build()?;
+        Ok(Self { url: url.into(), client, token })
+    }
+
+    /// Implements https://ethereum.github.io/keymanager-APIs/#/Local%20Key%20Manager/listKeys
+    pub async fn get_keystores(&self) -> Result<Vec<Keystore>> {
+        let url = self.url.join(KEYSTORES_PATH)?;
+        let mut request = self.client.get(url);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let res = request.send().await?;
+
+        let status = res.status();
+        let response_bytes = res.bytes().await?;
+
+        if !status.is_success() {
+            bail!(
+                "keymanager API request failed: status {status}; message: \"{}\"",
+                String::from_utf8_lossy(&response_bytes)
+            );
+        }
+
+        let response: BeaconApiResponse<Vec<Keystore>> = serde_json::from_slice(&response_bytes)?;
+        Ok(response.data)
+    }
+
+    /// Public keys of all the validators managed by the validator client
+    pub async fn get_pubkeys(&self) -> Result<Vec<BlsPublicKey>> {
+        let keystores = self.get_keystores().await?;
+        Ok(keystores.into_iter().map(|keystore| keystore.validating_pubkey).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_keystores() {
+        let data = r#"{
+            "data": [
+                {
+                    "validating_pubkey": "0x93247f2209abcacf57b75a51dafae777f9dd38bc7053d1af526f220a7489a6d3a2753e5f3e8b1cfe39b56f43611df74a",
+                    "derivation_path": "m/12381/3600/0/0/0",
+                    "readonly": true
+                },
+                {
+                    "validating_pubkey": "0xa9e9cff900de07e295a044789fd4bdb6785eb0651ad282f9e76d12afd87e75180bdd64caf2e315b815d7322bd31ab48a"
+                }
+            ]
+        }"#;
+
+        let keystores: BeaconApiResponse<Vec<Keystore>> = serde_json::from_str(data).unwrap();
+        assert_eq!(keystores.data.len(), 2);
+        assert!(keystores.data[0].readonly);
+        assert_eq!(keystores.data[0].derivation_path.as_deref(), Some("m/12381/3600/0/0/0"));
+        assert!(!keystores.data[1].readonly);
+        assert!(keystores.data[1].derivation_path.is_none());
+    }
+}
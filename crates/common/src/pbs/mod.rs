@@ -1,10 +1,16 @@
+mod beacon;
 mod constants;
 pub mod error;
 mod event;
+mod execution;
+mod keymanager;
 mod relay;
 mod types;
 
+pub use beacon::*;
 pub use constants::*;
 pub use event::*;
+pub use execution::*;
+pub use keymanager::*;
 pub use relay::*;
 pub use types::*;
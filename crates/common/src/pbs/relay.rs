@@ -1,23 +1,130 @@
-use std::{str::FromStr, sync::Arc};
+use std::{future::Future, str::FromStr, sync::Arc, time::Duration};
 
 use alloy::{
     primitives::{hex::FromHex, B256},
     rpc::types::beacon::BlsPublicKey,
 };
 use eyre::WrapErr;
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
-use serde::{Deserialize, Serialize};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::warn;
 use url::Url;
 
 use super::{
     constants::{BUILDER_API_PATH, GET_STATUS_PATH, REGISTER_VALIDATOR_PATH, SUBMIT_BLOCK_PATH},
     error::PbsError,
-    HEADER_VERSION_KEY, HEADER_VERSION_VALUE,
+    Version, HEADER_CONSENSUS_VERSION, HEADER_VERSION_KEY, HEADER_VERSION_VALUE,
+};
+use crate::{
+    config::{PbsClientConfig, RelayConfig},
+    utils::alloy_pubkey_to_blst,
+    DEFAULT_REQUEST_TIMEOUT,
 };
-use crate::{config::RelayConfig, DEFAULT_REQUEST_TIMEOUT};
 
+/// Default max size of a relay response in bytes, see
+/// [PbsClientConfig::max_payload_size_bytes]
 pub const MAX_SIZE: usize = 10 * 1024 * 1024;
 
+/// A relay url in the format scheme://pubkey@host, with the relay pubkey in
+/// the user info
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelayUrl {
+    url: Url,
+    pubkey: BlsPublicKey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RelayUrlError {
+    #[error("invalid url: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+
+    #[error("missing relay pubkey in url, expected scheme://pubkey@host")]
+    MissingPubkey,
+
+    #[error("invalid relay pubkey {0}")]
+    InvalidPubkey(String),
+
+    #[error("missing host in url")]
+    MissingHost,
+}
+
+impl RelayUrl {
+    pub fn pubkey(&self) -> BlsPublicKey {
+        self.pubkey
+    }
+
+    /// Url without the pubkey
+    pub fn endpoint(&self) -> Url {
+        let mut endpoint = self.url.clone();
+        // can't fail, the url has a host
+        let _ = endpoint.set_username("");
+        endpoint
+    }
+
+    /// Full url, including the pubkey
+    pub fn as_url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Host of the url, e.g. "relay.example.com"
+    pub fn host(&self) -> String {
+        // checked when parsing
+        self.url.host_str().unwrap_or_default().to_string()
+    }
+}
+
+impl TryFrom<Url> for RelayUrl {
+    type Error = RelayUrlError;
+
+    fn try_from(url: Url) -> Result<Self, Self::Error> {
+        if !url.has_host() {
+            return Err(RelayUrlError::MissingHost);
+        }
+
+        if url.username().is_empty() {
+            return Err(RelayUrlError::MissingPubkey);
+        }
+
+        let invalid_pubkey = || RelayUrlError::InvalidPubkey(url.username().to_string());
+        let pubkey = BlsPublicKey::from_hex(url.username()).map_err(|_| invalid_pubkey())?;
+        alloy_pubkey_to_blst(&pubkey).map_err(|_| invalid_pubkey())?;
+
+        Ok(Self { url, pubkey })
+    }
+}
+
+impl FromStr for RelayUrl {
+    type Err = RelayUrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Url::parse(s)?.try_into()
+    }
+}
+
+impl std::fmt::Display for RelayUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+impl Serialize for RelayUrl {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.url.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RelayUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Url::deserialize(deserializer)?.try_into().map_err(serde::de::Error::custom)
+    }
+}
+
 /// A parsed entry of the relay url in the format: scheme://pubkey@host
 #[derive(Debug, Clone)]
 pub struct RelayEntry {
@@ -43,11 +150,13 @@ impl<'de> Deserialize<'de> for RelayEntry {
     where
         D: serde::Deserializer<'de>,
     {
-        let url = Url::deserialize(deserializer)?;
-        let pubkey = BlsPublicKey::from_hex(url.username()).map_err(serde::de::Error::custom)?;
-        let id = url.host().ok_or(serde::de::Error::custom("missing host"))?.to_string();
+        let relay_url = RelayUrl::deserialize(deserializer)?;
 
-        Ok(RelayEntry { pubkey, url, id })
+        Ok(RelayEntry {
+            id: relay_url.host(),
+            pubkey: relay_url.pubkey(),
+            url: relay_url.as_url().clone(),
+        })
     }
 }
 
@@ -60,10 +169,23 @@ pub struct RelayClient {
     pub client: reqwest::Client,
     /// Configuration of the relay
     pub config: Arc<RelayConfig>,
+    /// Name of the relay set this relay belongs to, if any
+    pub relay_set: Option<Arc<String>>,
+    /// Max size of a response in bytes
+    max_payload_size: usize,
 }
 
 impl RelayClient {
     pub fn new(config: RelayConfig) -> eyre::Result<Self> {
+        Self::new_with_client_config(config, &PbsClientConfig::default())
+    }
+
+    /// Client with the HTTP settings of `client_config`. Timeouts set in the
+    /// relay config take precedence
+    pub fn new_with_client_config(
+        config: RelayConfig,
+        client_config: &PbsClientConfig,
+    ) -> eyre::Result<Self> {
         let mut headers = HeaderMap::new();
         headers.insert(HEADER_VERSION_KEY, HeaderValue::from_static(HEADER_VERSION_VALUE));
 
@@ -76,22 +198,87 @@ impl RelayClient {
             }
         }
 
+        let request_timeout =
+            config.request_timeout_ms.map(Duration::from_millis).unwrap_or(DEFAULT_REQUEST_TIMEOUT);
+        let connect_timeout_ms =
+            config.connect_timeout_ms.unwrap_or(client_config.connect_timeout_ms);
+
         let client = reqwest::Client::builder()
             .default_headers(headers)
-            .timeout(DEFAULT_REQUEST_TIMEOUT)
+            .user_agent(&client_config.user_agent)
+            .timeout(request_timeout)
+            .connect_timeout(Duration::from_millis(connect_timeout_ms))
             .build()?;
 
         Ok(Self {
             id: Arc::new(config.id.clone().unwrap_or(config.entry.id.clone())),
             client,
             config: Arc::new(config),
+            relay_set: None,
+            max_payload_size: client_config.max_payload_size_bytes,
         })
     }
 
+    pub fn with_relay_set(self, relay_set_name: Option<String>) -> Self {
+        Self { relay_set: relay_set_name.map(Arc::new), ..self }
+    }
+
+    /// Relay set used as metrics label, empty if not set
+    pub fn relay_set_label(&self) -> &str {
+        self.relay_set.as_deref().map_or("", String::as_str)
+    }
+
     pub fn pubkey(&self) -> BlsPublicKey {
         self.config.entry.pubkey
     }
 
+    /// Max size of a response in bytes, larger responses are rejected
+    pub fn max_payload_size(&self) -> usize {
+        self.max_payload_size
+    }
+
+    /// Sends a request with `f`, retrying timeouts and 5xx responses with
+    /// jittered exponential backoff according to the relay retry policy. Should
+    /// only be used for idempotent endpoints
+    pub async fn backoff_and_retry<T, F, Fut>(&self, endpoint: &str, f: F) -> Result<T, PbsError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, PbsError>>,
+    {
+        let policy = self.config.retry_policy;
+        let mut retries = 0;
+
+        loop {
+            match f().await {
+                Err(err) if retries < policy.max_retries && err.is_relay_down() => {
+                    retries += 1;
+                    let backoff = policy.backoff(retries);
+                    warn!(
+                        relay_id = %self.id,
+                        endpoint,
+                        retries,
+                        ?backoff,
+                        %err,
+                        "retrying relay request"
+                    );
+
+                    tokio::time::sleep(backoff).await;
+                }
+                Err(err) if retries > 0 => {
+                    warn!(
+                        relay_id = %self.id,
+                        endpoint,
+                        retries,
+                        %err,
+                        "relay request failed after retries"
+                    );
+                    return Err(err);
+                }
+                res => return res,
+            }
+        }
+    }
+
     // URL builders
     pub fn get_url(&self, path: &str) -> Result<Url, PbsError> {
         self.config.entry.url.join(path).map_err(PbsError::UrlParsing)
@@ -122,15 +309,98 @@ impl RelayClient {
     }
 }
 
+/// Checks that the consensus version of a relay response, if set, is the one
+/// of the supported payload types, so a relay on a different fork fails with
+/// a clear error instead of a decoding error
+pub fn check_consensus_version(headers: &HeaderMap) -> Result<(), PbsError> {
+    let Some(relay_version) = headers.get(HEADER_CONSENSUS_VERSION) else {
+        return Ok(());
+    };
+
+    let relay_version = String::from_utf8_lossy(relay_version.as_bytes()).to_lowercase();
+    let our_version = Version::default().as_str();
+    if relay_version == our_version {
+        Ok(())
+    } else {
+        Err(PbsError::VersionNegotiationFailed {
+            our_version: our_version.to_string(),
+            relay_version,
+        })
+    }
+}
+
+/// Reads the body of a relay response, failing as soon as it's larger than
+/// `max_size` instead of buffering it all. Checks the `Content-Length` first,
+/// if any
+pub async fn read_body_with_max(
+    mut res: reqwest::Response,
+    max_size: usize,
+) -> Result<Vec<u8>, PbsError> {
+    let content_length = res.content_length().map(|len| usize::try_from(len).unwrap_or(usize::MAX));
+    if let Some(payload_size) = content_length.filter(|len| *len > max_size) {
+        return Err(PbsError::PayloadTooLarge { payload_size });
+    }
+
+    let mut body = Vec::with_capacity(content_length.unwrap_or_default());
+    while let Some(chunk) = res.chunk().await? {
+        let payload_size = body.len() + chunk.len();
+        if payload_size > max_size {
+            return Err(PbsError::PayloadTooLarge { payload_size });
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+/// Checks that a relay response is JSON before decoding it, e.g. to catch html
+/// error pages from a proxy. Responses without a content type are accepted
+pub fn check_json_content_type(headers: &HeaderMap) -> Result<(), PbsError> {
+    let Some(content_type) = headers.get(CONTENT_TYPE) else {
+        return Ok(());
+    };
+
+    let content_type = String::from_utf8_lossy(content_type.as_bytes());
+    if content_type.to_lowercase().starts_with("application/json") {
+        Ok(())
+    } else {
+        Err(PbsError::InvalidContentType { got: content_type.into_owned() })
+    }
+}
+
+/// Decodes a relay response. Syntax errors are returned as
+/// [PbsError::SerdeDecodeError], valid json with invalid content as
+/// [PbsError::InvalidJsonContent] with the path of the invalid field
+pub fn decode_relay_response<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, PbsError> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(bytes);
+    serde_path_to_error::deserialize(deserializer).map_err(|err| {
+        if err.inner().is_data() {
+            PbsError::InvalidJsonContent {
+                field: err.path().to_string(),
+                detail: err.inner().to_string(),
+            }
+        } else {
+            PbsError::SerdeDecodeError(err.into_inner())
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use alloy::{
         primitives::{hex::FromHex, B256},
         rpc::types::beacon::BlsPublicKey,
     };
+    use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
 
-    use super::{RelayClient, RelayEntry};
-    use crate::config::RelayConfig;
+    use super::{
+        check_consensus_version, check_json_content_type, decode_relay_response,
+        read_body_with_max, RelayClient, RelayEntry, RelayUrl, RelayUrlError,
+    };
+    use crate::{
+        config::RelayConfig,
+        pbs::{error::PbsError, GetHeaderResponse, HEADER_CONSENSUS_VERSION},
+    };
 
     #[test]
     fn test_relay_entry() {
@@ -143,6 +413,90 @@ mod tests {
         assert_eq!(parsed.id, "abc.xyz");
     }
 
+    #[test]
+    fn test_relay_url() {
+        let pubkey = "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae";
+        let s = format!("https://{pubkey}@abc.xyz/");
+
+        let parsed: RelayUrl = s.parse().unwrap();
+        assert_eq!(parsed.pubkey(), BlsPublicKey::from_hex(pubkey).unwrap());
+        assert_eq!(parsed.endpoint().as_str(), "https://abc.xyz/");
+        assert_eq!(parsed.to_string(), s);
+
+        let json = serde_json::to_string(&parsed).unwrap();
+        assert_eq!(serde_json::from_str::<RelayUrl>(&json).unwrap(), parsed);
+
+        assert_eq!("https://abc.xyz".parse::<RelayUrl>(), Err(RelayUrlError::MissingPubkey));
+        assert_eq!(
+            "https://0x1234@abc.xyz".parse::<RelayUrl>(),
+            Err(RelayUrlError::InvalidPubkey("0x1234".to_string()))
+        );
+        let zero_pubkey = format!("0x{}", "00".repeat(48));
+        assert_eq!(
+            format!("https://{zero_pubkey}@abc.xyz").parse::<RelayUrl>(),
+            Err(RelayUrlError::InvalidPubkey(zero_pubkey))
+        );
+    }
+
+    #[test]
+    fn test_check_json_content_type() {
+        let mut headers = HeaderMap::new();
+        assert!(check_json_content_type(&headers).is_ok());
+
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json; charset=utf-8"));
+        assert!(check_json_content_type(&headers).is_ok());
+
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/html"));
+        assert!(matches!(
+            check_json_content_type(&headers),
+            Err(PbsError::InvalidContentType { got }) if got == "text/html"
+        ));
+    }
+
+    #[test]
+    fn test_check_consensus_version() {
+        let mut headers = HeaderMap::new();
+        assert!(check_consensus_version(&headers).is_ok());
+
+        headers.insert(HEADER_CONSENSUS_VERSION, HeaderValue::from_static("Deneb"));
+        assert!(check_consensus_version(&headers).is_ok());
+
+        headers.insert(HEADER_CONSENSUS_VERSION, HeaderValue::from_static("electra"));
+        assert!(matches!(
+            check_consensus_version(&headers),
+            Err(PbsError::VersionNegotiationFailed { our_version, relay_version })
+                if our_version == "deneb" && relay_version == "electra"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_body_with_max() {
+        let response = |body: &'static str| {
+            reqwest::Response::from(axum::http::Response::new(reqwest::Body::from(body)))
+        };
+
+        let body = read_body_with_max(response("{}"), 2).await.unwrap();
+        assert_eq!(body, b"{}");
+
+        assert!(matches!(
+            read_body_with_max(response("{ }"), 2).await,
+            Err(PbsError::PayloadTooLarge { payload_size: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_decode_relay_response() {
+        let res = decode_relay_response::<GetHeaderResponse>(b"{\"version\": \"deneb\",");
+        assert!(matches!(res, Err(PbsError::SerdeDecodeError(_))));
+
+        let data = r#"{"version": "deneb", "data": {"message": {"value": "not-a-number"}}}"#;
+        let res = decode_relay_response::<GetHeaderResponse>(data.as_bytes());
+        assert!(matches!(
+            res,
+            Err(PbsError::InvalidJsonContent { field, .. }) if field == "data.message.value"
+        ));
+    }
+
     #[test]
     fn test_relay_url() {
         let slot = 0;
@@ -176,4 +530,20 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn test_client_config() {
+        let relay_config = r#"
+        {
+            "url": "http://0xa1cec75a3f0661e99299274182938151e8433c61a19222347ea1313d839229cb4ce4e3e5aa2bdeb71c8fcf1b084963c2@abc.xyz"
+        }"#;
+        let config = serde_json::from_str::<RelayConfig>(relay_config).unwrap();
+
+        let relay = RelayClient::new(config.clone()).unwrap();
+        assert_eq!(relay.max_payload_size(), MAX_SIZE);
+
+        let client_config = PbsClientConfig { max_payload_size_bytes: 1024, ..Default::default() };
+        let relay = RelayClient::new_with_client_config(config, &client_config).unwrap();
+        assert_eq!(relay.max_payload_size(), 1024);
+    }
 }
@@ -4,11 +4,19 @@ use ssz_types::{FixedVector, VariableList};
 use tree_hash_derive::TreeHash;
 
 use super::spec::EthSpec;
-use crate::utils::as_str;
+use crate::{pbs::TARGET_BLOB_GAS_PER_BLOCK, utils::as_str};
 
 pub const EMPTY_TX_ROOT_HASH: B256 =
     b256!("7ffe241ea60187fdb0187bfa22de35d1f9bed7ab061d9401fd47e34a54fbede1");
 
+/// Expected `excess_blob_gas` of a block, derived from its parent
+/// https://eips.ethereum.org/EIPS/eip-4844#header-extension
+pub fn calc_excess_blob_gas(parent_excess_blob_gas: u64, parent_blob_gas_used: u64) -> u64 {
+    parent_excess_blob_gas
+        .saturating_add(parent_blob_gas_used)
+        .saturating_sub(TARGET_BLOB_GAS_PER_BLOCK)
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ExecutionPayload<T: EthSpec> {
     pub parent_hash: B256,
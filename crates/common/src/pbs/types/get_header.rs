@@ -44,7 +44,7 @@ pub struct SignedExecutionPayloadHeader {
 pub struct ExecutionPayloadHeaderMessage {
     pub header: ExecutionPayloadHeader<DenebSpec>,
     pub blob_kzg_commitments: KzgCommitments<DenebSpec>,
-    #[serde(with = "serde_utils::quoted_u256")]
+    #[serde(with = "crate::utils::eth_value")]
     pub value: U256,
     pub pubkey: BlsPublicKey,
 }
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::Chain;
+
+/// Response of the admin health endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthResponse {
+    /// Seconds since the module was started
+    pub uptime_secs: u64,
+    /// Version of commit-boost
+    pub version: String,
+    /// Chain the module is running on, in the same format as `CB_CHAIN`
+    pub chain: String,
+}
+
+/// Response of the admin stats endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsResponse {
+    /// Requests received since startup, excluding admin endpoints
+    pub requests: u64,
+    /// Requests that returned an error status code
+    pub errors: u64,
+    /// Seconds since the module was started
+    pub uptime_secs: u64,
+}
+
+/// Heartbeat periodically sent by the PBS module to signal it is alive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Heartbeat {
+    /// Unix timestamp in seconds
+    pub ts: u64,
+    pub chain: Chain,
+    /// Current slot according to the local clock
+    pub slot: u64,
+}
@@ -3,12 +3,17 @@ mod blinded_block_body;
 mod blobs_bundle;
 mod execution_payload;
 mod get_header;
+mod health;
 mod kzg;
 mod spec;
 mod utils;
 
 pub use beacon_block::{SignedBlindedBeaconBlock, SubmitBlindedBlockResponse};
-pub use execution_payload::{Transaction, EMPTY_TX_ROOT_HASH};
+pub use execution_payload::{
+    calc_excess_blob_gas, ExecutionPayload, Transaction, Withdrawal, EMPTY_TX_ROOT_HASH,
+};
 pub use get_header::{GetHeaderParams, GetHeaderResponse, SignedExecutionPayloadHeader};
+pub use health::{HealthResponse, Heartbeat, StatsResponse};
+pub use kzg::KzgCommitment;
 pub use spec::{DenebSpec, EthSpec};
 pub use utils::{Version, VersionedResponse};
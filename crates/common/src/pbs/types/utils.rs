@@ -41,3 +41,13 @@ pub enum Version {
     #[default]
     Deneb,
 }
+
+impl Version {
+    /// Lowercase fork name, as in the `version` field and the
+    /// `Eth-Consensus-Version` header
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Version::Deneb => "deneb",
+        }
+    }
+}
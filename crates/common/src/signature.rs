@@ -3,7 +3,7 @@ use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
 use crate::{
-    constants::{COMMIT_BOOST_DOMAIN, GENESIS_VALIDATORS_ROOT},
+    constants::{COMMIT_BOOST_DOMAIN, DOMAIN_APPLICATION_MASK, GENESIS_VALIDATORS_ROOT},
     error::BlstErrorWrapper,
     signer::{schemes::bls::verify_bls_signature, BlsSecretKey},
     types::Chain,
@@ -25,8 +25,14 @@ pub fn compute_signing_root(object_root: [u8; 32], signing_domain: [u8; 32]) ->
     signing_data.tree_hash_root().0
 }
 
-// NOTE: this currently works only for builder domain signatures and
-// verifications
+/// Whether `domain_mask` is an application domain, e.g. builder or commit-boost
+/// signatures, rather than a beacon chain domain
+pub fn is_application_domain(domain_mask: [u8; 4]) -> bool {
+    domain_mask.iter().zip(DOMAIN_APPLICATION_MASK).any(|(byte, mask)| byte & mask != 0)
+}
+
+// NOTE: application domains always use the genesis fork version and a zero
+// genesis validators root, beacon chain domains use the root of the chain
 // ref: https://github.com/ralexstokes/ethereum-consensus/blob/cf3c404043230559660810bc0c9d6d5a8498d819/ethereum-consensus/src/builder/mod.rs#L26-L29
 pub fn compute_domain(chain: Chain, domain_mask: [u8; 4]) -> [u8; 32] {
     #[derive(Debug, TreeHash)]
@@ -39,7 +45,12 @@ pub fn compute_domain(chain: Chain, domain_mask: [u8; 4]) -> [u8; 32] {
     domain[..4].copy_from_slice(&domain_mask);
 
     let fork_version = chain.genesis_fork_version();
-    let fd = ForkData { fork_version, genesis_validators_root: GENESIS_VALIDATORS_ROOT };
+    let genesis_validators_root = if is_application_domain(domain_mask) {
+        GENESIS_VALIDATORS_ROOT
+    } else {
+        chain.genesis_validators_root()
+    };
+    let fd = ForkData { fork_version, genesis_validators_root };
     let fork_data_root = fd.tree_hash_root();
 
     domain[4..].copy_from_slice(&fork_data_root[..28]);
@@ -55,6 +66,16 @@ pub fn verify_signed_message<T: TreeHash>(
     domain_mask: [u8; 4],
 ) -> Result<(), BlstErrorWrapper> {
     let domain = compute_domain(chain, domain_mask);
+    verify_signed_message_with_domain(pubkey, msg, signature, domain)
+}
+
+/// Like [verify_signed_message], but with an already computed signing domain
+pub fn verify_signed_message_with_domain<T: TreeHash>(
+    pubkey: &BlsPublicKey,
+    msg: &T,
+    signature: &BlsSignature,
+    domain: [u8; 32],
+) -> Result<(), BlstErrorWrapper> {
     let signing_root = compute_signing_root(msg.tree_hash_root().0, domain);
 
     verify_bls_signature(pubkey, &signing_root, signature)
@@ -88,11 +109,29 @@ pub fn sign_commit_boost_root(
     sign_message(secret_key, &signing_root)
 }
 
+pub fn verify_commit_boost_root(
+    chain: Chain,
+    pubkey: &BlsPublicKey,
+    object_root: [u8; 32],
+    signature: &BlsSignature,
+) -> Result<(), BlstErrorWrapper> {
+    let domain = compute_domain(chain, COMMIT_BOOST_DOMAIN);
+    let signing_root = compute_signing_root(object_root, domain);
+    verify_bls_signature(pubkey, &signing_root, signature)
+}
+
 #[cfg(test)]
 mod tests {
 
-    use super::compute_domain;
-    use crate::{constants::APPLICATION_BUILDER_DOMAIN, types::Chain};
+    use super::{
+        compute_domain, is_application_domain, sign_commit_boost_root, verify_commit_boost_root,
+    };
+    use crate::{
+        constants::{APPLICATION_BUILDER_DOMAIN, COMMIT_BOOST_DOMAIN},
+        signer::schemes::bls::random_secret,
+        types::Chain,
+        utils::blst_pubkey_to_alloy,
+    };
 
     #[test]
     fn test_builder_domains() {
@@ -104,9 +143,50 @@ mod tests {
             compute_domain(Chain::Holesky, APPLICATION_BUILDER_DOMAIN),
             Chain::Holesky.builder_domain()
         );
+        assert_eq!(
+            compute_domain(Chain::Sepolia, APPLICATION_BUILDER_DOMAIN),
+            Chain::Sepolia.builder_domain()
+        );
+        assert_eq!(
+            compute_domain(Chain::Gnosis, APPLICATION_BUILDER_DOMAIN),
+            Chain::Gnosis.builder_domain()
+        );
         assert_eq!(
             compute_domain(Chain::Helder, APPLICATION_BUILDER_DOMAIN),
             Chain::Helder.builder_domain()
         );
     }
+
+    #[test]
+    fn test_chain_compute_domain() {
+        let chains = [Chain::Mainnet, Chain::Holesky, Chain::Sepolia, Chain::Gnosis, Chain::Helder];
+        for chain in chains {
+            assert_eq!(chain.compute_domain(APPLICATION_BUILDER_DOMAIN), chain.builder_domain());
+            assert_eq!(
+                chain.compute_domain(COMMIT_BOOST_DOMAIN),
+                compute_domain(chain, COMMIT_BOOST_DOMAIN)
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_application_domain() {
+        assert!(is_application_domain(APPLICATION_BUILDER_DOMAIN));
+        assert!(is_application_domain(COMMIT_BOOST_DOMAIN));
+        // DOMAIN_BEACON_PROPOSER
+        assert!(!is_application_domain([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_verify_commit_boost_root() {
+        let secret_key = random_secret();
+        let pubkey = blst_pubkey_to_alloy(&secret_key.sk_to_pk());
+        let object_root = [1; 32];
+
+        let signature = sign_commit_boost_root(Chain::Holesky, &secret_key, object_root);
+
+        assert!(verify_commit_boost_root(Chain::Holesky, &pubkey, object_root, &signature).is_ok());
+        assert!(verify_commit_boost_root(Chain::Holesky, &pubkey, [2; 32], &signature).is_err());
+        assert!(verify_commit_boost_root(Chain::Mainnet, &pubkey, object_root, &signature).is_err());
+    }
 }
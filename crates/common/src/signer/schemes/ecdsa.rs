@@ -94,7 +94,7 @@ impl fmt::Display for EcdsaPublicKey {
     }
 }
 
-#[derive(Clone, Deref, Serialize, Deserialize)]
+#[derive(Debug, Clone, Deref, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct EcdsaSignature {
     #[serde(with = "alloy::hex::serde")]
@@ -1,28 +1,144 @@
-use std::path::PathBuf;
+use std::{
+    cmp::Ordering, collections::HashMap, path::PathBuf, str::FromStr, sync::Once, time::Duration,
+};
 
-use alloy::primitives::{hex, Bytes};
+use alloy::primitives::{hex, Bytes, B256};
 use derive_more::{Deref, Display, From, Into};
 use eyre::{bail, Context};
 use serde::{Deserialize, Serialize};
+use tracing::warn;
+use url::Url;
 
-use crate::{constants::APPLICATION_BUILDER_DOMAIN, signature::compute_domain};
+use crate::{
+    constants::{APPLICATION_BUILDER_DOMAIN, GENESIS_VALIDATORS_ROOT, SLOTS_PER_EPOCH},
+    signature::{compute_domain, compute_signing_root},
+    utils::utcnow_sec,
+};
 
-#[derive(Clone, Debug, Display, PartialEq, Eq, Hash, Deref, From, Into, Serialize, Deserialize)]
+#[derive(Clone, Debug, Display, PartialEq, Eq, Hash, Deref, From, Into, Serialize)]
 #[into(owned, ref, ref_mut)]
 #[serde(transparent)]
 pub struct ModuleId(pub String);
 
+/// Max length of a [ModuleId]
+pub const MODULE_ID_MAX_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ModuleIdError {
+    #[error("module id is empty")]
+    Empty,
+
+    #[error("module id is too long: {len} chars, max {max}")]
+    TooLong { len: usize, max: usize },
+
+    #[error("invalid character {0:?} in module id, only [a-zA-Z0-9_-] are allowed")]
+    InvalidChar(char),
+}
+
+impl ModuleId {
+    /// Checks that the id is non-empty, at most [MODULE_ID_MAX_LEN] chars and
+    /// only contains `[a-zA-Z0-9_-]`, since it's used in urls, env var and
+    /// container names
+    pub fn validate(&self) -> Result<(), ModuleIdError> {
+        if self.0.is_empty() {
+            return Err(ModuleIdError::Empty);
+        }
+
+        let allowed = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+        if let Some(c) = self.0.chars().find(|c| !allowed(*c)) {
+            return Err(ModuleIdError::InvalidChar(c));
+        }
+
+        if self.0.len() > MODULE_ID_MAX_LEN {
+            return Err(ModuleIdError::TooLong { len: self.0.len(), max: MODULE_ID_MAX_LEN });
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for ModuleId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let id = ModuleId(String::deserialize(deserializer)?);
+        id.validate().map_err(serde::de::Error::custom)?;
+        Ok(id)
+    }
+}
+
 #[derive(Clone, Debug, Display, PartialEq, Eq, Hash, Deref, From, Into, Serialize, Deserialize)]
 #[into(owned, ref, ref_mut)]
 #[serde(transparent)]
 pub struct Jwt(pub String);
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Chain {
     Mainnet,
     Holesky,
+    Sepolia,
+    Gnosis,
     Helder,
-    Custom { genesis_time_secs: u64, slot_time_secs: u64, genesis_fork_version: [u8; 4] },
+    Custom {
+        genesis_time_secs: u64,
+        slot_time_secs: u64,
+        genesis_fork_version: [u8; 4],
+        /// Chain id of the execution layer, if known
+        evm_chain_id: Option<u64>,
+        /// Genesis validators root used to compute signing domains, if
+        /// missing a zero root is used
+        genesis_validators_root: Option<[u8; 32]>,
+        /// Forks activated after genesis, if missing the genesis fork version
+        /// is used for every slot
+        fork_schedule: Option<ForkSchedule>,
+    },
+}
+
+/// A fork activated at `epoch`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Fork {
+    pub epoch: u64,
+    #[serde(with = "serde_utils::bytes_4_hex")]
+    pub version: [u8; 4],
+}
+
+/// Forks activated after genesis, `None` for forks that are not scheduled
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct ForkSchedule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub altair: Option<Fork>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bellatrix: Option<Fork>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capella: Option<Fork>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deneb: Option<Fork>,
+}
+
+impl ForkSchedule {
+    /// Forks by lowercase name, in activation order
+    fn forks(&self) -> [(&'static str, Option<Fork>); 4] {
+        [
+            ("altair", self.altair),
+            ("bellatrix", self.bellatrix),
+            ("capella", self.capella),
+            ("deneb", self.deneb),
+        ]
+    }
+
+    /// Version of the latest fork activated at or before `epoch`, or
+    /// `genesis_fork_version` if none is
+    pub fn fork_version_at_epoch(&self, genesis_fork_version: [u8; 4], epoch: u64) -> [u8; 4] {
+        [self.altair, self.bellatrix, self.capella, self.deneb]
+            .into_iter()
+            .flatten()
+            .filter(|fork| fork.epoch <= epoch)
+            .max_by_key(|fork| fork.epoch)
+            .map_or(genesis_fork_version, |fork| fork.version)
+    }
 }
 
 impl std::fmt::Debug for Chain {
@@ -30,40 +146,194 @@ impl std::fmt::Debug for Chain {
         match self {
             Self::Mainnet => write!(f, "Mainnet"),
             Self::Holesky => write!(f, "Holesky"),
+            Self::Sepolia => write!(f, "Sepolia"),
+            Self::Gnosis => write!(f, "Gnosis"),
             Self::Helder => write!(f, "Helder"),
-            Self::Custom { genesis_time_secs, slot_time_secs, genesis_fork_version } => f
+            Self::Custom {
+                genesis_time_secs,
+                slot_time_secs,
+                genesis_fork_version,
+                evm_chain_id,
+                genesis_validators_root,
+                fork_schedule,
+            } => f
                 .debug_struct("Custom")
                 .field("genesis_time_secs", genesis_time_secs)
                 .field("slot_time_secs", slot_time_secs)
                 .field("genesis_fork_version", &hex::encode_prefixed(genesis_fork_version))
+                .field("evm_chain_id", evm_chain_id)
+                .field(
+                    "genesis_validators_root",
+                    &genesis_validators_root.map(hex::encode_prefixed),
+                )
+                .field("fork_schedule", fork_schedule)
                 .finish(),
         }
     }
 }
 
+/// Lowercase name for known chains, e.g. "holesky", and
+/// "custom(genesis=..,slot=..,fork=0x..)" for custom chains, followed by the
+/// optional `chain_id`, `root` and `schedule` fields when set, e.g.
+/// "schedule=altair:10:0x02000000;capella:20:0x03000000". Parses back with
+/// [FromStr]
+impl std::fmt::Display for Chain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mainnet => write!(f, "mainnet"),
+            Self::Holesky => write!(f, "holesky"),
+            Self::Sepolia => write!(f, "sepolia"),
+            Self::Gnosis => write!(f, "gnosis"),
+            Self::Helder => write!(f, "helder"),
+            Self::Custom {
+                genesis_time_secs,
+                slot_time_secs,
+                genesis_fork_version,
+                evm_chain_id,
+                genesis_validators_root,
+                fork_schedule,
+            } => {
+                write!(
+                    f,
+                    "custom(genesis={genesis_time_secs},slot={slot_time_secs},fork={}",
+                    hex::encode_prefixed(genesis_fork_version)
+                )?;
+                if let Some(id) = evm_chain_id {
+                    write!(f, ",chain_id={id}")?;
+                }
+                if let Some(root) = genesis_validators_root {
+                    write!(f, ",root={}", hex::encode_prefixed(root))?;
+                }
+                if let Some(schedule) = fork_schedule {
+                    let forks: Vec<_> = schedule
+                        .forks()
+                        .into_iter()
+                        .filter_map(|(name, fork)| {
+                            let fork = fork?;
+                            Some(format!(
+                                "{name}:{}:{}",
+                                fork.epoch,
+                                hex::encode_prefixed(fork.version)
+                            ))
+                        })
+                        .collect();
+                    write!(f, ",schedule={}", forks.join(";"))?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Known chains come first, sorted by genesis time. Custom chains follow,
+/// sorted by genesis time, slot time and genesis fork version, then by the
+/// optional fields so the order is consistent with [PartialEq]
+impl Ord for Chain {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (
+                Chain::Custom {
+                    genesis_time_secs,
+                    slot_time_secs,
+                    genesis_fork_version,
+                    evm_chain_id,
+                    genesis_validators_root,
+                    fork_schedule,
+                },
+                Chain::Custom {
+                    genesis_time_secs: other_genesis_time_secs,
+                    slot_time_secs: other_slot_time_secs,
+                    genesis_fork_version: other_genesis_fork_version,
+                    evm_chain_id: other_evm_chain_id,
+                    genesis_validators_root: other_genesis_validators_root,
+                    fork_schedule: other_fork_schedule,
+                },
+            ) => (genesis_time_secs, slot_time_secs, genesis_fork_version)
+                .cmp(&(other_genesis_time_secs, other_slot_time_secs, other_genesis_fork_version))
+                .then_with(|| {
+                    (evm_chain_id, genesis_validators_root, fork_schedule).cmp(&(
+                        other_evm_chain_id,
+                        other_genesis_validators_root,
+                        other_fork_schedule,
+                    ))
+                }),
+            (Chain::Custom { .. }, _) => Ordering::Greater,
+            (_, Chain::Custom { .. }) => Ordering::Less,
+            _ => self.genesis_time_sec().cmp(&other.genesis_time_sec()),
+        }
+    }
+}
+
+impl PartialOrd for Chain {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Chain {
+    /// Whether this is mainnet, including custom chains with the mainnet
+    /// genesis fork version
+    pub fn is_mainnet(&self) -> bool {
+        self.genesis_fork_version() == KnownChain::Mainnet.genesis_fork_version()
+    }
+
     pub fn builder_domain(&self) -> [u8; 32] {
         match self {
             Chain::Mainnet => KnownChain::Mainnet.builder_domain(),
             Chain::Holesky => KnownChain::Holesky.builder_domain(),
+            Chain::Sepolia => KnownChain::Sepolia.builder_domain(),
+            Chain::Gnosis => KnownChain::Gnosis.builder_domain(),
             Chain::Helder => KnownChain::Helder.builder_domain(),
-            Chain::Custom { .. } => compute_domain(*self, APPLICATION_BUILDER_DOMAIN),
+            Chain::Custom { .. } => self.compute_domain(APPLICATION_BUILDER_DOMAIN),
         }
     }
 
+    /// Signing domain for `domain_type` on this chain, see
+    /// [compute_domain](crate::signature::compute_domain)
+    pub fn compute_domain(&self, domain_type: [u8; 4]) -> [u8; 32] {
+        compute_domain(*self, domain_type)
+    }
+
     pub fn genesis_fork_version(&self) -> [u8; 4] {
         match self {
             Chain::Mainnet => KnownChain::Mainnet.genesis_fork_version(),
             Chain::Holesky => KnownChain::Holesky.genesis_fork_version(),
+            Chain::Sepolia => KnownChain::Sepolia.genesis_fork_version(),
+            Chain::Gnosis => KnownChain::Gnosis.genesis_fork_version(),
             Chain::Helder => KnownChain::Helder.genesis_fork_version(),
             Chain::Custom { genesis_fork_version, .. } => *genesis_fork_version,
         }
     }
 
+    /// Genesis validators root used to compute beacon chain signing domains.
+    /// Known chains and custom chains without a configured root use a zero
+    /// root. Application domains, e.g. builder, always use a zero root
+    pub fn genesis_validators_root(&self) -> [u8; 32] {
+        static WARN_MISSING_ROOT: Once = Once::new();
+
+        match self {
+            Chain::Mainnet | Chain::Holesky | Chain::Sepolia | Chain::Gnosis | Chain::Helder => {
+                GENESIS_VALIDATORS_ROOT
+            }
+            Chain::Custom { genesis_validators_root: Some(root), .. } => *root,
+            Chain::Custom { genesis_validators_root: None, .. } => {
+                WARN_MISSING_ROOT.call_once(|| {
+                    warn!(
+                        "genesis_validators_root not set for custom chain, using a zero root for \
+                         beacon chain domains"
+                    )
+                });
+                GENESIS_VALIDATORS_ROOT
+            }
+        }
+    }
+
     pub fn genesis_time_sec(&self) -> u64 {
         match self {
             Chain::Mainnet => KnownChain::Mainnet.genesis_time_sec(),
             Chain::Holesky => KnownChain::Holesky.genesis_time_sec(),
+            Chain::Sepolia => KnownChain::Sepolia.genesis_time_sec(),
+            Chain::Gnosis => KnownChain::Gnosis.genesis_time_sec(),
             Chain::Helder => KnownChain::Helder.genesis_time_sec(),
             Chain::Custom { genesis_time_secs, .. } => *genesis_time_secs,
         }
@@ -73,18 +343,183 @@ impl Chain {
         match self {
             Chain::Mainnet => KnownChain::Mainnet.slot_time_sec(),
             Chain::Holesky => KnownChain::Holesky.slot_time_sec(),
+            Chain::Sepolia => KnownChain::Sepolia.slot_time_sec(),
+            Chain::Gnosis => KnownChain::Gnosis.slot_time_sec(),
             Chain::Helder => KnownChain::Helder.slot_time_sec(),
             Chain::Custom { slot_time_secs, .. } => *slot_time_secs,
         }
     }
+
+    /// Chain id of the execution layer, `None` for custom chains without a
+    /// configured `chain_id`
+    pub fn evm_chain_id(&self) -> Option<u64> {
+        match self {
+            Chain::Mainnet => Some(KnownChain::Mainnet.evm_chain_id()),
+            Chain::Holesky => Some(KnownChain::Holesky.evm_chain_id()),
+            Chain::Sepolia => Some(KnownChain::Sepolia.evm_chain_id()),
+            Chain::Gnosis => Some(KnownChain::Gnosis.evm_chain_id()),
+            Chain::Helder => Some(KnownChain::Helder.evm_chain_id()),
+            Chain::Custom { evm_chain_id, .. } => *evm_chain_id,
+        }
+    }
+
+    /// Known chain with the same parameters as this custom chain, if any,
+    /// otherwise `self`. Optional parameters that are not set are ignored.
+    /// The genesis validators root is not compared, known chains are
+    /// identified by their fork versions
+    pub fn normalize(&self) -> Self {
+        let Chain::Custom {
+            genesis_time_secs,
+            slot_time_secs,
+            genesis_fork_version,
+            evm_chain_id,
+            fork_schedule,
+            ..
+        } = *self
+        else {
+            return *self;
+        };
+
+        KnownChain::all()
+            .into_iter()
+            .find(|known| {
+                known.genesis_time_sec() == genesis_time_secs &&
+                    known.slot_time_sec() == slot_time_secs &&
+                    known.genesis_fork_version() == genesis_fork_version &&
+                    evm_chain_id.map_or(true, |id| id == known.evm_chain_id()) &&
+                    fork_schedule.map_or(true, |schedule| schedule == known.fork_schedule())
+            })
+            .map_or(*self, Chain::from)
+    }
+
+    /// Forks activated after genesis, empty for custom chains without a
+    /// configured `fork_schedule`
+    pub fn fork_schedule(&self) -> ForkSchedule {
+        match self {
+            Chain::Mainnet => KnownChain::Mainnet.fork_schedule(),
+            Chain::Holesky => KnownChain::Holesky.fork_schedule(),
+            Chain::Sepolia => KnownChain::Sepolia.fork_schedule(),
+            Chain::Gnosis => KnownChain::Gnosis.fork_schedule(),
+            Chain::Helder => KnownChain::Helder.fork_schedule(),
+            Chain::Custom { fork_schedule, .. } => fork_schedule.unwrap_or_default(),
+        }
+    }
+
+    /// Capella fork version, `None` for custom chains without capella in the
+    /// fork schedule
+    pub fn capella_fork_version(&self) -> Option<[u8; 4]> {
+        self.fork_schedule().capella.map(|fork| fork.version)
+    }
+
+    /// Deneb fork version, `None` for custom chains without deneb in the fork
+    /// schedule
+    pub fn deneb_fork_version(&self) -> Option<[u8; 4]> {
+        self.fork_schedule().deneb.map(|fork| fork.version)
+    }
+
+    /// Fork version active at `slot`, used to compute domains other than the
+    /// builder domain, which always uses the genesis fork version
+    pub fn fork_version_at_slot(&self, slot: u64) -> [u8; 4] {
+        let epoch = slot / self.slots_per_epoch();
+        self.fork_schedule().fork_version_at_epoch(self.genesis_fork_version(), epoch)
+    }
+
+    /// Gnosis uses 16 slots per epoch, all other chains 32
+    pub fn slots_per_epoch(&self) -> u64 {
+        match self {
+            Chain::Gnosis => 16,
+            _ => SLOTS_PER_EPOCH,
+        }
+    }
+
+    /// Current slot according to the local clock, 0 before genesis
+    pub fn current_slot(&self) -> u64 {
+        utcnow_sec().saturating_sub(self.genesis_time_sec()) / self.slot_time_sec()
+    }
+
+    /// Current epoch according to the local clock, 0 before genesis
+    pub fn current_epoch(&self) -> u64 {
+        self.current_slot() / self.slots_per_epoch()
+    }
+
+    /// Unix timestamp in seconds of the start of `slot`
+    pub fn slot_to_timestamp(&self, slot: u64) -> u64 {
+        self.genesis_time_sec() + slot * self.slot_time_sec()
+    }
+}
+
+/// First slot of the forks activated after genesis, `None` for forks that are
+/// not scheduled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ForkSlots {
+    pub bellatrix: Option<u64>,
+    pub capella: Option<u64>,
+    pub deneb: Option<u64>,
+}
+
+/// A [Chain] with the activation slots of its forks, to check which fork is
+/// active at a slot without going through the fork schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainSpec {
+    pub chain: Chain,
+    pub fork_slots: ForkSlots,
+}
+
+impl ChainSpec {
+    /// Fork slots from the fork schedule of the chain, known chains have all
+    /// the forks scheduled while custom chains only have those configured in
+    /// `fork_schedule`. Forks with an epoch too large to be converted to a
+    /// slot, e.g. `FAR_FUTURE_EPOCH` in spec files, are not scheduled
+    pub fn from_chain(chain: Chain) -> Self {
+        let schedule = chain.fork_schedule();
+        let slot = |fork: Option<Fork>| {
+            fork.and_then(|fork| fork.epoch.checked_mul(chain.slots_per_epoch()))
+        };
+
+        let fork_slots = ForkSlots {
+            bellatrix: slot(schedule.bellatrix),
+            capella: slot(schedule.capella),
+            deneb: slot(schedule.deneb),
+        };
+
+        Self { chain, fork_slots }
+    }
+
+    pub fn is_post_bellatrix(&self, slot: u64) -> bool {
+        self.fork_slots.bellatrix.is_some_and(|fork_slot| slot >= fork_slot)
+    }
+
+    pub fn is_post_capella(&self, slot: u64) -> bool {
+        self.fork_slots.capella.is_some_and(|fork_slot| slot >= fork_slot)
+    }
+
+    pub fn is_post_deneb(&self, slot: u64) -> bool {
+        self.fork_slots.deneb.is_some_and(|fork_slot| slot >= fork_slot)
+    }
+
+    /// Signing root of `object_root` with the domain of `domain_type` on this
+    /// chain, see [Chain::compute_domain]
+    pub fn compute_signed_root(&self, object_root: [u8; 32], domain_type: [u8; 4]) -> [u8; 32] {
+        compute_signing_root(object_root, self.chain.compute_domain(domain_type))
+    }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+impl From<Chain> for ChainSpec {
+    fn from(chain: Chain) -> Self {
+        Self::from_chain(chain)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum KnownChain {
     #[serde(alias = "mainnet")]
     Mainnet,
     #[serde(alias = "holesky")]
     Holesky,
+    #[serde(alias = "sepolia")]
+    Sepolia,
+    #[serde(alias = "gnosis")]
+    Gnosis,
     #[serde(alias = "helder")]
     Helder,
 }
@@ -101,6 +536,14 @@ impl KnownChain {
                 0, 0, 0, 1, 91, 131, 162, 55, 89, 197, 96, 178, 208, 198, 69, 118, 225, 220, 252,
                 52, 234, 148, 196, 152, 143, 62, 13, 159, 119, 240, 83, 135,
             ],
+            KnownChain::Sepolia => [
+                0, 0, 0, 1, 211, 1, 7, 120, 205, 8, 238, 81, 75, 8, 254, 103, 182, 197, 3, 181, 16,
+                152, 122, 76, 228, 63, 66, 48, 109, 151, 198, 124,
+            ],
+            KnownChain::Gnosis => [
+                0, 0, 0, 1, 249, 37, 221, 197, 226, 174, 99, 117, 44, 204, 147, 184, 227, 236, 188,
+                20, 37, 156, 27, 52, 71, 70, 78, 141, 73, 64, 35, 12,
+            ],
             KnownChain::Helder => [
                 0, 0, 0, 1, 148, 196, 26, 244, 132, 255, 247, 150, 73, 105, 224, 189, 217, 34, 248,
                 45, 255, 15, 75, 232, 122, 96, 208, 102, 76, 201, 209, 255,
@@ -112,6 +555,8 @@ impl KnownChain {
         match self {
             KnownChain::Mainnet => [0u8; 4],
             KnownChain::Holesky => [1, 1, 112, 0],
+            KnownChain::Sepolia => [144, 0, 0, 105],
+            KnownChain::Gnosis => [0, 0, 0, 100],
             KnownChain::Helder => [16, 0, 0, 0],
         }
     }
@@ -120,15 +565,203 @@ impl KnownChain {
         match self {
             KnownChain::Mainnet => 1606824023,
             KnownChain::Holesky => 1695902400,
+            KnownChain::Sepolia => 1655733600,
+            KnownChain::Gnosis => 1638993340,
             KnownChain::Helder => 1718967660,
         }
     }
 
+    pub fn fork_schedule(&self) -> ForkSchedule {
+        let fork = |epoch, version| Some(Fork { epoch, version });
+
+        match self {
+            KnownChain::Mainnet => ForkSchedule {
+                altair: fork(74240, [1, 0, 0, 0]),
+                bellatrix: fork(144896, [2, 0, 0, 0]),
+                capella: fork(194048, [3, 0, 0, 0]),
+                deneb: fork(269568, [4, 0, 0, 0]),
+            },
+            KnownChain::Holesky => ForkSchedule {
+                altair: fork(0, [2, 1, 112, 0]),
+                bellatrix: fork(0, [3, 1, 112, 0]),
+                capella: fork(256, [4, 1, 112, 0]),
+                deneb: fork(29696, [5, 1, 112, 0]),
+            },
+            KnownChain::Sepolia => ForkSchedule {
+                altair: fork(50, [144, 0, 0, 112]),
+                bellatrix: fork(100, [144, 0, 0, 113]),
+                capella: fork(56832, [144, 0, 0, 114]),
+                deneb: fork(132608, [144, 0, 0, 115]),
+            },
+            KnownChain::Gnosis => ForkSchedule {
+                altair: fork(512, [1, 0, 0, 100]),
+                bellatrix: fork(385536, [2, 0, 0, 100]),
+                capella: fork(648704, [3, 0, 0, 100]),
+                deneb: fork(889856, [4, 0, 0, 100]),
+            },
+            KnownChain::Helder => ForkSchedule {
+                altair: fork(0, [32, 0, 0, 0]),
+                bellatrix: fork(0, [48, 0, 0, 0]),
+                capella: fork(0, [64, 0, 0, 0]),
+                deneb: fork(0, [80, 19, 39, 54]),
+            },
+        }
+    }
+
+    pub fn evm_chain_id(&self) -> u64 {
+        match self {
+            KnownChain::Mainnet => 1,
+            KnownChain::Holesky => 17000,
+            KnownChain::Sepolia => 11155111,
+            KnownChain::Gnosis => 100,
+            KnownChain::Helder => 7014190335,
+        }
+    }
+
     pub fn slot_time_sec(&self) -> u64 {
         match self {
-            KnownChain::Mainnet | KnownChain::Holesky | KnownChain::Helder => 12,
+            KnownChain::Mainnet |
+            KnownChain::Holesky |
+            KnownChain::Sepolia |
+            KnownChain::Helder => 12,
+            KnownChain::Gnosis => 5,
+        }
+    }
+}
+
+/// Result of detecting a chain from its genesis fork version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainDetection {
+    Known(KnownChain),
+    /// Fork version not matching any known chain, e.g. a custom devnet
+    Unknown([u8; 4]),
+}
+
+impl KnownChain {
+    /// All known chains
+    pub const fn all() -> [KnownChain; 5] {
+        [
+            KnownChain::Mainnet,
+            KnownChain::Holesky,
+            KnownChain::Sepolia,
+            KnownChain::Gnosis,
+            KnownChain::Helder,
+        ]
+    }
+
+    /// Builder domains of all known chains, e.g. to detect signatures for a
+    /// different chain
+    pub fn all_builder_domains() -> HashMap<KnownChain, [u8; 32]> {
+        KnownChain::all().into_iter().map(|chain| (chain, chain.builder_domain())).collect()
+    }
+
+    /// Detect a known chain from its genesis fork version
+    pub fn detect(genesis_fork_version: [u8; 4]) -> ChainDetection {
+        KnownChain::all()
+            .into_iter()
+            .find(|chain| chain.genesis_fork_version() == genesis_fork_version)
+            .map_or(ChainDetection::Unknown(genesis_fork_version), ChainDetection::Known)
+    }
+}
+
+/// Error returned when parsing an unknown chain name
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "unknown chain: {0}, expected one of: mainnet, holesky, sepolia, gnosis, helder or \
+     custom(genesis=..,slot=..,fork=0x..)"
+)]
+pub struct ParseChainError(pub String);
+
+/// Accepts the same names as the config, e.g. "Holesky" or "holesky"
+impl FromStr for KnownChain {
+    type Err = ParseChainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Mainnet" | "mainnet" => Ok(KnownChain::Mainnet),
+            "Holesky" | "holesky" => Ok(KnownChain::Holesky),
+            "Sepolia" | "sepolia" => Ok(KnownChain::Sepolia),
+            "Gnosis" | "gnosis" => Ok(KnownChain::Gnosis),
+            "Helder" | "helder" => Ok(KnownChain::Helder),
+            _ => Err(ParseChainError(s.to_string())),
+        }
+    }
+}
+
+/// Also accepts custom chains in the format of the [Display] impl
+impl FromStr for Chain {
+    type Err = ParseChainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("custom(").and_then(|fields| fields.strip_suffix(')')) {
+            Some(fields) => {
+                parse_custom_chain(fields).ok_or_else(|| ParseChainError(s.to_string()))
+            }
+            None => KnownChain::from_str(s).map(Chain::from),
+        }
+    }
+}
+
+fn parse_custom_chain(fields: &str) -> Option<Chain> {
+    let mut genesis_time_secs = None;
+    let mut slot_time_secs = None;
+    let mut genesis_fork_version = None;
+    let mut evm_chain_id = None;
+    let mut genesis_validators_root = None;
+    let mut fork_schedule = None;
+
+    for field in fields.split(',') {
+        match field.split_once('=')? {
+            ("genesis", value) => genesis_time_secs = Some(value.parse().ok()?),
+            ("slot", value) => slot_time_secs = Some(value.parse().ok()?),
+            ("fork", value) => {
+                genesis_fork_version = Some(hex::decode(value).ok()?.try_into().ok()?)
+            }
+            ("chain_id", value) => evm_chain_id = Some(value.parse().ok()?),
+            ("root", value) => {
+                genesis_validators_root = Some(hex::decode(value).ok()?.try_into().ok()?)
+            }
+            ("schedule", value) => fork_schedule = Some(parse_fork_schedule(value)?),
+            _ => return None,
+        }
+    }
+
+    Some(Chain::Custom {
+        genesis_time_secs: genesis_time_secs?,
+        slot_time_secs: slot_time_secs?,
+        genesis_fork_version: genesis_fork_version?,
+        evm_chain_id,
+        genesis_validators_root,
+        fork_schedule,
+    })
+}
+
+/// Parses `name:epoch:0xversion` forks separated by `;`, the format of the
+/// [Display] impl of [Chain]
+fn parse_fork_schedule(value: &str) -> Option<ForkSchedule> {
+    let mut schedule = ForkSchedule::default();
+
+    for fork in value.split(';').filter(|fork| !fork.is_empty()) {
+        let mut parts = fork.split(':');
+        let (name, epoch, version) = (parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let fork = Some(Fork {
+            epoch: epoch.parse().ok()?,
+            version: hex::decode(version).ok()?.try_into().ok()?,
+        });
+        match name {
+            "altair" => schedule.altair = fork,
+            "bellatrix" => schedule.bellatrix = fork,
+            "capella" => schedule.capella = fork,
+            "deneb" => schedule.deneb = fork,
+            _ => return None,
         }
     }
+
+    Some(schedule)
 }
 
 impl From<KnownChain> for Chain {
@@ -136,6 +769,8 @@ impl From<KnownChain> for Chain {
         match value {
             KnownChain::Mainnet => Chain::Mainnet,
             KnownChain::Holesky => Chain::Holesky,
+            KnownChain::Sepolia => Chain::Sepolia,
+            KnownChain::Gnosis => Chain::Gnosis,
             KnownChain::Helder => Chain::Helder,
         }
     }
@@ -146,7 +781,17 @@ impl From<KnownChain> for Chain {
 enum ChainLoader {
     Known(KnownChain),
     Path(PathBuf),
-    Custom { genesis_time_secs: u64, slot_time_secs: u64, genesis_fork_version: Bytes },
+    Custom {
+        genesis_time_secs: u64,
+        slot_time_secs: u64,
+        genesis_fork_version: Bytes,
+        #[serde(default, alias = "chain_id", skip_serializing_if = "Option::is_none")]
+        evm_chain_id: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        genesis_validators_root: Option<B256>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fork_schedule: Option<ForkSchedule>,
+    },
 }
 
 impl Serialize for Chain {
@@ -157,14 +802,24 @@ impl Serialize for Chain {
         let loader = match self {
             Chain::Mainnet => ChainLoader::Known(KnownChain::Mainnet),
             Chain::Holesky => ChainLoader::Known(KnownChain::Holesky),
+            Chain::Sepolia => ChainLoader::Known(KnownChain::Sepolia),
+            Chain::Gnosis => ChainLoader::Known(KnownChain::Gnosis),
             Chain::Helder => ChainLoader::Known(KnownChain::Helder),
-            Chain::Custom { genesis_time_secs, slot_time_secs, genesis_fork_version } => {
-                ChainLoader::Custom {
-                    genesis_time_secs: *genesis_time_secs,
-                    slot_time_secs: *slot_time_secs,
-                    genesis_fork_version: Bytes::from(*genesis_fork_version),
-                }
-            }
+            Chain::Custom {
+                genesis_time_secs,
+                slot_time_secs,
+                genesis_fork_version,
+                evm_chain_id,
+                genesis_validators_root,
+                fork_schedule,
+            } => ChainLoader::Custom {
+                genesis_time_secs: *genesis_time_secs,
+                slot_time_secs: *slot_time_secs,
+                genesis_fork_version: Bytes::from(*genesis_fork_version),
+                evm_chain_id: *evm_chain_id,
+                genesis_validators_root: genesis_validators_root.map(B256::from),
+                fork_schedule: *fork_schedule,
+            },
         };
 
         loader.serialize(serializer)
@@ -181,51 +836,139 @@ impl<'de> Deserialize<'de> for Chain {
         match loader {
             ChainLoader::Known(known) => Ok(Chain::from(known)),
             ChainLoader::Path(path) => load_chain_from_file(path).map_err(serde::de::Error::custom),
-            ChainLoader::Custom { genesis_time_secs, slot_time_secs, genesis_fork_version } => {
+            ChainLoader::Custom {
+                genesis_time_secs,
+                slot_time_secs,
+                genesis_fork_version,
+                evm_chain_id,
+                genesis_validators_root,
+                fork_schedule,
+            } => {
                 let genesis_fork_version: [u8; 4] =
                     genesis_fork_version.as_ref().try_into().map_err(serde::de::Error::custom)?;
-                Ok(Chain::Custom { genesis_time_secs, slot_time_secs, genesis_fork_version })
+                Ok(Chain::Custom {
+                    genesis_time_secs,
+                    slot_time_secs,
+                    genesis_fork_version,
+                    evm_chain_id,
+                    genesis_validators_root: genesis_validators_root.map(|root| root.0),
+                    fork_schedule,
+                })
             }
         }
     }
 }
 
-/// Load a chain config from a spec file, such as returned by
-/// /eth/v1/config/spec ref: https://ethereum.github.io/beacon-APIs/#/Config/getSpec
-/// Try to load two formats:
-/// - JSON as return the getSpec endpoint, either with or without the `data`
-///   field
-/// - YAML as used e.g. in Kurtosis/Ethereum Package
-pub fn load_chain_from_file(path: PathBuf) -> eyre::Result<Chain> {
-    #[derive(Deserialize)]
-    #[serde(rename_all = "UPPERCASE")]
-    struct QuotedSpecFile {
-        #[serde(with = "serde_utils::quoted_u64")]
-        min_genesis_time: u64,
-        #[serde(with = "serde_utils::quoted_u64")]
-        genesis_delay: u64,
-        #[serde(with = "serde_utils::quoted_u64")]
-        seconds_per_slot: u64,
-        genesis_fork_version: Bytes,
+/// Spec as returned by the getSpec endpoint, with quoted numbers
+#[derive(Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct QuotedSpecFile {
+    #[serde(with = "serde_utils::quoted_u64")]
+    min_genesis_time: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    genesis_delay: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    seconds_per_slot: u64,
+    genesis_fork_version: Bytes,
+    #[serde(default)]
+    deposit_chain_id: Option<String>,
+    #[serde(flatten)]
+    forks: SpecForks,
+}
+
+impl QuotedSpecFile {
+    fn to_chain(&self) -> eyre::Result<Chain> {
+        let genesis_fork_version: [u8; 4] = self.genesis_fork_version.as_ref().try_into()?;
+        let evm_chain_id = self.deposit_chain_id.as_deref().map(str::parse::<u64>).transpose()?;
+
+        Ok(Chain::Custom {
+            genesis_time_secs: self.min_genesis_time + self.genesis_delay,
+            slot_time_secs: self.seconds_per_slot,
+            genesis_fork_version,
+            evm_chain_id,
+            genesis_validators_root: None,
+            fork_schedule: self.forks.to_fork_schedule()?,
+        })
     }
+}
 
-    impl QuotedSpecFile {
-        fn to_chain(&self) -> eyre::Result<Chain> {
-            let genesis_fork_version: [u8; 4] = self.genesis_fork_version.as_ref().try_into()?;
+/// Fork epochs and versions of a spec file, quoted in JSON and plain numbers
+/// in YAML
+#[derive(Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct SpecForks {
+    altair_fork_epoch: Option<SpecValue>,
+    altair_fork_version: Option<SpecValue>,
+    bellatrix_fork_epoch: Option<SpecValue>,
+    bellatrix_fork_version: Option<SpecValue>,
+    capella_fork_epoch: Option<SpecValue>,
+    capella_fork_version: Option<SpecValue>,
+    deneb_fork_epoch: Option<SpecValue>,
+    deneb_fork_version: Option<SpecValue>,
+}
 
-            Ok(Chain::Custom {
-                genesis_time_secs: self.min_genesis_time + self.genesis_delay,
-                slot_time_secs: self.seconds_per_slot,
-                genesis_fork_version,
-            })
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SpecValue {
+    Number(u64),
+    String(String),
+}
+
+impl SpecValue {
+    fn to_epoch(&self) -> eyre::Result<u64> {
+        match self {
+            SpecValue::Number(n) => Ok(*n),
+            SpecValue::String(s) => Ok(s.parse()?),
         }
     }
 
-    #[derive(Deserialize)]
-    struct SpecFileJson {
-        data: QuotedSpecFile,
+    fn to_fork_version(&self) -> eyre::Result<[u8; 4]> {
+        match self {
+            SpecValue::Number(n) => Ok(u32::try_from(*n)?.to_be_bytes()),
+            SpecValue::String(s) => Ok(hex::decode(s)?.as_slice().try_into()?),
+        }
+    }
+}
+
+impl SpecForks {
+    /// `None` if the spec has no forks
+    fn to_fork_schedule(&self) -> eyre::Result<Option<ForkSchedule>> {
+        fn fork(
+            epoch: &Option<SpecValue>,
+            version: &Option<SpecValue>,
+        ) -> eyre::Result<Option<Fork>> {
+            match (epoch, version) {
+                (Some(epoch), Some(version)) => {
+                    Ok(Some(Fork { epoch: epoch.to_epoch()?, version: version.to_fork_version()? }))
+                }
+                _ => Ok(None),
+            }
+        }
+
+        let schedule = ForkSchedule {
+            altair: fork(&self.altair_fork_epoch, &self.altair_fork_version)?,
+            bellatrix: fork(&self.bellatrix_fork_epoch, &self.bellatrix_fork_version)?,
+            capella: fork(&self.capella_fork_epoch, &self.capella_fork_version)?,
+            deneb: fork(&self.deneb_fork_epoch, &self.deneb_fork_version)?,
+        };
+
+        Ok((schedule != ForkSchedule::default()).then_some(schedule))
     }
+}
+
+#[derive(Deserialize)]
+struct SpecFileJson {
+    data: QuotedSpecFile,
+}
 
+/// Load a chain config from a spec file, such as returned by
+/// /eth/v1/config/spec ref: https://ethereum.github.io/beacon-APIs/#/Config/getSpec
+/// Try to load three formats:
+/// - JSON as return the getSpec endpoint, either with or without the `data`
+///   field
+/// - YAML as used e.g. in Kurtosis/Ethereum Package
+/// - TOML with the same fields as the YAML format
+pub fn load_chain_from_file(path: PathBuf) -> eyre::Result<Chain> {
     #[derive(Deserialize)]
     #[serde(rename_all = "UPPERCASE")]
     struct SpecFile {
@@ -233,17 +976,24 @@ pub fn load_chain_from_file(path: PathBuf) -> eyre::Result<Chain> {
         genesis_delay: u64,
         seconds_per_slot: u64,
         genesis_fork_version: u32,
+        #[serde(default)]
+        deposit_chain_id: Option<u64>,
+        #[serde(flatten)]
+        forks: SpecForks,
     }
 
     impl SpecFile {
-        fn to_chain(&self) -> Chain {
+        fn to_chain(&self) -> eyre::Result<Chain> {
             let genesis_fork_version: [u8; 4] = self.genesis_fork_version.to_be_bytes();
 
-            Chain::Custom {
+            Ok(Chain::Custom {
                 genesis_time_secs: self.min_genesis_time + self.genesis_delay,
                 slot_time_secs: self.seconds_per_slot,
                 genesis_fork_version,
-            }
+                evm_chain_id: self.deposit_chain_id,
+                genesis_validators_root: None,
+                fork_schedule: self.forks.to_fork_schedule()?,
+            })
         }
     }
 
@@ -255,26 +1005,405 @@ pub fn load_chain_from_file(path: PathBuf) -> eyre::Result<Chain> {
     } else if let Ok(decoded) = serde_json::from_slice::<QuotedSpecFile>(&file) {
         decoded.to_chain()
     } else if let Ok(decoded) = serde_yaml::from_slice::<SpecFile>(&file) {
-        Ok(decoded.to_chain())
+        decoded.to_chain()
+    } else if let Some(decoded) =
+        std::str::from_utf8(&file).ok().and_then(|file| toml::from_str::<SpecFile>(file).ok())
+    {
+        decoded.to_chain()
     } else {
-        bail!("unable to decode file: {path:?}, accepted formats are: json or yml")
+        bail!("unable to decode file: {path:?}, accepted formats are: json, yml or toml")
+    }
+}
+
+/// Default timeout for [load_chain_from_url]
+pub const CHAIN_SPEC_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Load a chain config from the getSpec endpoint of a beacon node, e.g.
+/// http://localhost:5052/eth/v1/config/spec
+pub async fn load_chain_from_url(url: &str) -> eyre::Result<Chain> {
+    load_chain_from_url_with_timeout(url, CHAIN_SPEC_REQUEST_TIMEOUT).await
+}
+
+/// Like [load_chain_from_url] with a custom request timeout. Timeouts,
+/// connection errors and 5xx responses are retried once
+pub async fn load_chain_from_url_with_timeout(url: &str, timeout: Duration) -> eyre::Result<Chain> {
+    async fn fetch_spec(client: &reqwest::Client, url: Url) -> reqwest::Result<SpecFileJson> {
+        client.get(url).send().await?.error_for_status()?.json().await
+    }
+
+    let url: Url = url.parse().wrap_err(format!("invalid chain spec url: {url}"))?;
+    let client = reqwest::Client::builder().timeout(timeout).build()?;
+
+    let spec = match fetch_spec(&client, url.clone()).await {
+        Err(err) if is_transient(&err) => {
+            warn!(%err, %url, "failed to fetch chain spec, retrying");
+            fetch_spec(&client, url.clone()).await
+        }
+        res => res,
     }
+    .wrap_err(format!("unable to fetch chain spec from {url}"))?;
+
+    spec.data.to_chain()
+}
+
+fn is_transient(err: &reqwest::Error) -> bool {
+    err.is_timeout() ||
+        err.is_connect() ||
+        err.status().is_some_and(|status| status.is_server_error())
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use super::*;
+    use crate::constants::COMMIT_BOOST_DOMAIN;
 
     #[derive(Debug, Serialize, Deserialize)]
     struct MockConfig {
         chain: Chain,
     }
 
+    #[test]
+    fn test_module_id_validate() {
+        assert!(ModuleId("DA_COMMIT".into()).validate().is_ok());
+        assert!(ModuleId("my-module-2".into()).validate().is_ok());
+        assert!(ModuleId("a".repeat(MODULE_ID_MAX_LEN)).validate().is_ok());
+
+        assert_eq!(ModuleId("".into()).validate(), Err(ModuleIdError::Empty));
+        assert_eq!(ModuleId("my/module".into()).validate(), Err(ModuleIdError::InvalidChar('/')));
+        assert_eq!(ModuleId("my module".into()).validate(), Err(ModuleIdError::InvalidChar(' ')));
+        assert_eq!(ModuleId("m\0".into()).validate(), Err(ModuleIdError::InvalidChar('\0')));
+        assert_eq!(
+            ModuleId("a".repeat(MODULE_ID_MAX_LEN + 1)).validate(),
+            Err(ModuleIdError::TooLong { len: MODULE_ID_MAX_LEN + 1, max: MODULE_ID_MAX_LEN })
+        );
+
+        let id: ModuleId = serde_json::from_str("\"DA_COMMIT\"").unwrap();
+        assert_eq!(id, ModuleId("DA_COMMIT".into()));
+        assert!(serde_json::from_str::<ModuleId>("\"DA COMMIT\"").is_err());
+    }
+
+    #[test]
+    fn test_detect_chain() {
+        assert_eq!(KnownChain::detect([0, 0, 0, 0]), ChainDetection::Known(KnownChain::Mainnet));
+        assert_eq!(KnownChain::detect([1, 1, 112, 0]), ChainDetection::Known(KnownChain::Holesky));
+        assert_eq!(
+            KnownChain::detect([144, 0, 0, 105]),
+            ChainDetection::Known(KnownChain::Sepolia)
+        );
+        assert_eq!(KnownChain::detect([0, 0, 0, 100]), ChainDetection::Known(KnownChain::Gnosis));
+        assert_eq!(KnownChain::detect([16, 0, 0, 0]), ChainDetection::Known(KnownChain::Helder));
+        assert_eq!(KnownChain::detect([1, 2, 3, 4]), ChainDetection::Unknown([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_all_builder_domains() {
+        let domains = KnownChain::all_builder_domains();
+        assert_eq!(domains.len(), KnownChain::all().len());
+        assert_eq!(domains[&KnownChain::Holesky], Chain::Holesky.builder_domain());
+        assert_eq!(domains[&KnownChain::Gnosis], Chain::Gnosis.builder_domain());
+    }
+
+    #[test]
+    fn test_chain_from_str() {
+        assert_eq!("Mainnet".parse(), Ok(Chain::Mainnet));
+        assert_eq!("holesky".parse(), Ok(Chain::Holesky));
+        assert_eq!("sepolia".parse(), Ok(Chain::Sepolia));
+        assert_eq!("Gnosis".parse(), Ok(Chain::Gnosis));
+        assert_eq!("helder".parse(), Ok(Chain::Helder));
+        assert_eq!("HOLESKY".parse::<Chain>(), Err(ParseChainError("HOLESKY".to_string())));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(Chain::Holesky.normalize(), Chain::Holesky);
+
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("tests/data/holesky_spec_data.json");
+        let chain = load_chain_from_file(path).unwrap();
+        assert!(matches!(chain, Chain::Custom { .. }));
+        assert_eq!(chain.normalize(), Chain::Holesky);
+
+        let custom = |genesis_fork_version, genesis_validators_root| Chain::Custom {
+            genesis_time_secs: KnownChain::Mainnet.genesis_time_sec(),
+            slot_time_secs: KnownChain::Mainnet.slot_time_sec(),
+            genesis_fork_version,
+            evm_chain_id: None,
+            genesis_validators_root,
+            fork_schedule: None,
+        };
+        let mainnet_fork_version = KnownChain::Mainnet.genesis_fork_version();
+        assert_eq!(custom(mainnet_fork_version, None).normalize(), Chain::Mainnet);
+        assert_eq!(custom(mainnet_fork_version, Some([1; 32])).normalize(), Chain::Mainnet);
+        assert_eq!(custom([1; 4], None).normalize(), custom([1; 4], None));
+    }
+
+    #[test]
+    fn test_chain_display() {
+        for chain in KnownChain::all() {
+            let chain = Chain::from(chain);
+            assert_eq!(chain.to_string().parse(), Ok(chain));
+        }
+        assert_eq!(Chain::Holesky.to_string(), "holesky");
+
+        let custom = Chain::Custom {
+            genesis_time_secs: 1,
+            slot_time_secs: 2,
+            genesis_fork_version: [1, 0, 0, 0],
+            evm_chain_id: None,
+            genesis_validators_root: None,
+            fork_schedule: None,
+        };
+        assert_eq!(custom.to_string(), "custom(genesis=1,slot=2,fork=0x01000000)");
+        assert_eq!(custom.to_string().parse(), Ok(custom));
+
+        let fork_schedule = ForkSchedule {
+            altair: Some(Fork { epoch: 10, version: [2, 0, 0, 0] }),
+            capella: Some(Fork { epoch: 20, version: [3, 0, 0, 0] }),
+            ..Default::default()
+        };
+        for fork_schedule in [fork_schedule, ForkSchedule::default()] {
+            let custom = Chain::Custom {
+                genesis_time_secs: 1,
+                slot_time_secs: 2,
+                genesis_fork_version: [1, 0, 0, 0],
+                evm_chain_id: Some(3),
+                genesis_validators_root: Some([4; 32]),
+                fork_schedule: Some(fork_schedule),
+            };
+            assert_eq!(custom.to_string().parse(), Ok(custom));
+        }
+
+        assert!("custom(genesis=1,slot=2)".parse::<Chain>().is_err());
+        assert!("custom(genesis=1,slot=2,fork=0x01)".parse::<Chain>().is_err());
+    }
+
+    #[test]
+    fn test_chain_hash() {
+        let custom = |genesis_time_secs| Chain::Custom {
+            genesis_time_secs,
+            slot_time_secs: 2,
+            genesis_fork_version: [1, 0, 0, 0],
+            evm_chain_id: None,
+            genesis_validators_root: None,
+            fork_schedule: None,
+        };
+
+        let mut map = HashMap::new();
+        map.insert(Chain::Holesky, 1);
+        map.insert(custom(1), 2);
+
+        assert_eq!(map.get(&Chain::Holesky), Some(&1));
+        assert_eq!(map.get(&custom(1)), Some(&2));
+        assert_eq!(map.get(&custom(2)), None);
+        assert_eq!(map.get(&Chain::Mainnet), None);
+    }
+
+    #[test]
+    fn test_chain_ord() {
+        let custom = |genesis_time_secs, slot_time_secs, genesis_fork_version| Chain::Custom {
+            genesis_time_secs,
+            slot_time_secs,
+            genesis_fork_version,
+            evm_chain_id: None,
+            genesis_validators_root: None,
+            fork_schedule: None,
+        };
+
+        let chains: BTreeSet<_> = [
+            custom(2, 1, [0, 0, 0, 0]),
+            custom(1, 2, [0, 0, 0, 0]),
+            custom(1, 1, [0, 0, 0, 1]),
+            custom(1, 1, [0, 0, 0, 0]),
+            Chain::Helder,
+            Chain::Holesky,
+            Chain::Sepolia,
+            Chain::Gnosis,
+            Chain::Mainnet,
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(chains.into_iter().collect::<Vec<_>>(), vec![
+            Chain::Mainnet,
+            Chain::Gnosis,
+            Chain::Sepolia,
+            Chain::Holesky,
+            Chain::Helder,
+            custom(1, 1, [0, 0, 0, 0]),
+            custom(1, 1, [0, 0, 0, 1]),
+            custom(1, 2, [0, 0, 0, 0]),
+            custom(2, 1, [0, 0, 0, 0]),
+        ]);
+
+        // custom chains with the parameters of a known chain are still distinct
+        let mainnet = Chain::Mainnet;
+        let custom_mainnet = custom(
+            mainnet.genesis_time_sec(),
+            mainnet.slot_time_sec(),
+            mainnet.genesis_fork_version(),
+        );
+        assert!(custom_mainnet > Chain::Mainnet);
+
+        let with_chain_id = Chain::Custom {
+            genesis_time_secs: 1,
+            slot_time_secs: 1,
+            genesis_fork_version: [0, 0, 0, 0],
+            evm_chain_id: Some(1),
+            genesis_validators_root: None,
+            fork_schedule: None,
+        };
+        assert_eq!(with_chain_id.cmp(&custom(1, 1, [0, 0, 0, 0])), Ordering::Greater);
+        assert_eq!(with_chain_id.cmp(&with_chain_id), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_current_slot() {
+        let chain = Chain::Custom {
+            genesis_time_secs: utcnow_sec() - 120,
+            slot_time_secs: 12,
+            genesis_fork_version: [1, 0, 0, 0],
+            evm_chain_id: None,
+            genesis_validators_root: None,
+            fork_schedule: None,
+        };
+        assert!((9..=10).contains(&chain.current_slot()));
+
+        let chain = Chain::Custom {
+            genesis_time_secs: utcnow_sec() + 120,
+            slot_time_secs: 12,
+            genesis_fork_version: [1, 0, 0, 0],
+            evm_chain_id: None,
+            genesis_validators_root: None,
+            fork_schedule: None,
+        };
+        assert_eq!(chain.current_slot(), 0);
+    }
+
+    #[test]
+    fn test_slot_to_timestamp() {
+        assert_eq!(Chain::Mainnet.slot_to_timestamp(0), 1606824023);
+        assert_eq!(Chain::Mainnet.slot_to_timestamp(10), 1606824023 + 120);
+        assert_eq!(Chain::Gnosis.slot_to_timestamp(10), 1638993340 + 50);
+
+        for chain in [Chain::Mainnet, Chain::Holesky, Chain::Gnosis] {
+            let slot = chain.current_slot();
+            let now = utcnow_sec();
+            assert!(chain.slot_to_timestamp(slot) <= now);
+            assert!(chain.slot_to_timestamp(slot + 1) > now - 1);
+            assert!(chain.current_epoch().abs_diff(slot / chain.slots_per_epoch()) <= 1);
+        }
+    }
+
+    #[test]
+    fn test_fork_version_at_slot() {
+        assert_eq!(Chain::Mainnet.fork_version_at_slot(0), [0, 0, 0, 0]);
+        assert_eq!(Chain::Mainnet.fork_version_at_slot(74240 * 32 - 1), [0, 0, 0, 0]);
+        assert_eq!(Chain::Mainnet.fork_version_at_slot(74240 * 32), [1, 0, 0, 0]);
+        assert_eq!(Chain::Mainnet.fork_version_at_slot(269568 * 32), [4, 0, 0, 0]);
+        assert_eq!(Chain::Holesky.fork_version_at_slot(0), [3, 1, 112, 0]);
+        assert_eq!(Chain::Gnosis.fork_version_at_slot(512 * 16), [1, 0, 0, 100]);
+        assert_eq!(Chain::Helder.fork_version_at_slot(0), [80, 19, 39, 54]);
+
+        let s = r#"chain = { genesis_time_secs = 1, slot_time_secs = 2, genesis_fork_version = "0x01000000", fork_schedule = { capella = { epoch = 10, version = "0x03000000" } } }"#;
+        let decoded: MockConfig = toml::from_str(s).unwrap();
+        assert_eq!(decoded.chain.fork_version_at_slot(10 * 32 - 1), [1, 0, 0, 0]);
+        assert_eq!(decoded.chain.fork_version_at_slot(10 * 32), [3, 0, 0, 0]);
+
+        let encoded = toml::to_string(&decoded).unwrap();
+        let decoded_again: MockConfig = toml::from_str(&encoded).unwrap();
+        assert_eq!(decoded_again.chain, decoded.chain);
+
+        let s = r#"chain = { genesis_time_secs = 1, slot_time_secs = 2, genesis_fork_version = "0x01000000" }"#;
+        let decoded: MockConfig = toml::from_str(s).unwrap();
+        assert_eq!(decoded.chain.fork_version_at_slot(u64::MAX), [1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_capella_deneb_fork_versions() {
+        assert_eq!(Chain::Mainnet.capella_fork_version(), Some([3, 0, 0, 0]));
+        assert_eq!(Chain::Holesky.deneb_fork_version(), Some([5, 1, 112, 0]));
+
+        let custom = Chain::Custom {
+            genesis_time_secs: 1,
+            slot_time_secs: 2,
+            genesis_fork_version: [1, 0, 0, 0],
+            evm_chain_id: None,
+            genesis_validators_root: None,
+            fork_schedule: None,
+        };
+        assert_eq!(custom.capella_fork_version(), None);
+    }
+
+    #[test]
+    fn test_chain_spec() {
+        let spec = ChainSpec::from_chain(Chain::Mainnet);
+        assert_eq!(spec.fork_slots.capella, Some(194048 * 32));
+        assert!(spec.is_post_bellatrix(194048 * 32));
+        assert!(spec.is_post_capella(194048 * 32));
+        assert!(!spec.is_post_capella(194048 * 32 - 1));
+        assert!(!spec.is_post_deneb(194048 * 32));
+
+        let spec = ChainSpec::from_chain(Chain::Gnosis);
+        assert_eq!(spec.fork_slots.deneb, Some(889856 * 16));
+
+        let spec = ChainSpec::from_chain(Chain::Helder);
+        assert!(spec.is_post_deneb(0));
+
+        let custom = Chain::Custom {
+            genesis_time_secs: 1,
+            slot_time_secs: 2,
+            genesis_fork_version: [1, 0, 0, 0],
+            evm_chain_id: None,
+            genesis_validators_root: None,
+            fork_schedule: None,
+        };
+        let spec = ChainSpec::from_chain(custom);
+        assert_eq!(spec.fork_slots, ForkSlots::default());
+        assert!(!spec.is_post_bellatrix(u64::MAX));
+
+        const FAR_FUTURE_EPOCH: u64 = u64::MAX;
+        let unscheduled_deneb = Chain::Custom {
+            genesis_time_secs: 1,
+            slot_time_secs: 2,
+            genesis_fork_version: [1, 0, 0, 0],
+            evm_chain_id: None,
+            genesis_validators_root: None,
+            fork_schedule: Some(ForkSchedule {
+                capella: Some(Fork { epoch: 10, version: [3, 0, 0, 0] }),
+                deneb: Some(Fork { epoch: FAR_FUTURE_EPOCH, version: [4, 0, 0, 0] }),
+                ..Default::default()
+            }),
+        };
+        let spec = ChainSpec::from_chain(unscheduled_deneb);
+        assert_eq!(spec.fork_slots.capella, Some(10 * 32));
+        assert_eq!(spec.fork_slots.deneb, None);
+        assert!(!spec.is_post_deneb(u64::MAX));
+
+        let root = [1; 32];
+        let spec = ChainSpec::from_chain(Chain::Holesky);
+        assert_eq!(
+            spec.compute_signed_root(root, APPLICATION_BUILDER_DOMAIN),
+            compute_signing_root(root, Chain::Holesky.builder_domain())
+        );
+    }
+
     #[test]
     fn test_load_known() {
         let s = r#"chain = "Mainnet""#;
         let decoded: MockConfig = toml::from_str(s).unwrap();
         assert_eq!(decoded.chain, Chain::Mainnet);
+
+        let s = r#"chain = "sepolia""#;
+        let decoded: MockConfig = toml::from_str(s).unwrap();
+        assert_eq!(decoded.chain, Chain::Sepolia);
+
+        let s = r#"chain = "gnosis""#;
+        let decoded: MockConfig = toml::from_str(s).unwrap();
+        assert_eq!(decoded.chain, Chain::Gnosis);
     }
 
     #[test]
@@ -284,10 +1413,70 @@ mod tests {
         assert_eq!(decoded.chain, Chain::Custom {
             genesis_time_secs: 1,
             slot_time_secs: 2,
-            genesis_fork_version: [1, 0, 0, 0]
+            genesis_fork_version: [1, 0, 0, 0],
+            evm_chain_id: None,
+            genesis_validators_root: None,
+            fork_schedule: None,
         })
     }
 
+    #[test]
+    fn test_load_custom_chain_id() {
+        let s = r#"chain = { genesis_time_secs = 1, slot_time_secs = 2, genesis_fork_version = "0x01000000", chain_id = 12345 }"#;
+        let decoded: MockConfig = toml::from_str(s).unwrap();
+        assert_eq!(decoded.chain, Chain::Custom {
+            genesis_time_secs: 1,
+            slot_time_secs: 2,
+            genesis_fork_version: [1, 0, 0, 0],
+            evm_chain_id: Some(12345),
+            genesis_validators_root: None,
+            fork_schedule: None,
+        });
+        assert_eq!(decoded.chain.evm_chain_id(), Some(12345));
+        assert_eq!(Chain::Holesky.evm_chain_id(), Some(17000));
+
+        let encoded = toml::to_string(&decoded).unwrap();
+        let decoded_again: MockConfig = toml::from_str(&encoded).unwrap();
+        assert_eq!(decoded_again.chain, decoded.chain);
+    }
+
+    #[test]
+    fn test_load_custom_genesis_validators_root() {
+        let s = r#"chain = { genesis_time_secs = 1, slot_time_secs = 2, genesis_fork_version = "0x01000000", genesis_validators_root = "0x0101010101010101010101010101010101010101010101010101010101010101" }"#;
+        let decoded: MockConfig = toml::from_str(s).unwrap();
+        assert_eq!(decoded.chain.genesis_validators_root(), [1; 32]);
+
+        let encoded = toml::to_string(&decoded).unwrap();
+        let decoded_again: MockConfig = toml::from_str(&encoded).unwrap();
+        assert_eq!(decoded.chain, decoded_again.chain);
+
+        let Chain::Custom { genesis_time_secs, slot_time_secs, genesis_fork_version, .. } =
+            decoded.chain
+        else {
+            panic!("expected custom chain");
+        };
+        let zero_root = Chain::Custom {
+            genesis_time_secs,
+            slot_time_secs,
+            genesis_fork_version,
+            evm_chain_id: None,
+            genesis_validators_root: None,
+            fork_schedule: None,
+        };
+        // builder signatures always use a zero root, beacon chain domains use the
+        // configured one
+        assert_eq!(decoded.chain.builder_domain(), zero_root.builder_domain());
+        assert_eq!(
+            decoded.chain.compute_domain(COMMIT_BOOST_DOMAIN),
+            zero_root.compute_domain(COMMIT_BOOST_DOMAIN)
+        );
+        let beacon_proposer_domain = [0, 0, 0, 0];
+        assert_ne!(
+            decoded.chain.compute_domain(beacon_proposer_domain),
+            zero_root.compute_domain(beacon_proposer_domain)
+        );
+    }
+
     #[test]
     fn test_load_file_data_json() {
         let a = env!("CARGO_MANIFEST_DIR");
@@ -303,7 +1492,10 @@ mod tests {
         assert_eq!(decoded.chain, Chain::Custom {
             genesis_time_secs: KnownChain::Holesky.genesis_time_sec(),
             slot_time_secs: KnownChain::Holesky.slot_time_sec(),
-            genesis_fork_version: KnownChain::Holesky.genesis_fork_version()
+            genesis_fork_version: KnownChain::Holesky.genesis_fork_version(),
+            evm_chain_id: Some(17000),
+            genesis_validators_root: None,
+            fork_schedule: Some(KnownChain::Holesky.fork_schedule()),
         })
     }
 
@@ -322,7 +1514,10 @@ mod tests {
         assert_eq!(decoded.chain, Chain::Custom {
             genesis_time_secs: KnownChain::Holesky.genesis_time_sec(),
             slot_time_secs: KnownChain::Holesky.slot_time_sec(),
-            genesis_fork_version: KnownChain::Holesky.genesis_fork_version()
+            genesis_fork_version: KnownChain::Holesky.genesis_fork_version(),
+            evm_chain_id: Some(17000),
+            genesis_validators_root: None,
+            fork_schedule: Some(KnownChain::Holesky.fork_schedule()),
         })
     }
 
@@ -341,7 +1536,32 @@ mod tests {
         assert_eq!(decoded.chain, Chain::Custom {
             genesis_time_secs: KnownChain::Helder.genesis_time_sec(),
             slot_time_secs: KnownChain::Helder.slot_time_sec(),
-            genesis_fork_version: KnownChain::Helder.genesis_fork_version()
+            genesis_fork_version: KnownChain::Helder.genesis_fork_version(),
+            evm_chain_id: Some(7014190335),
+            genesis_validators_root: None,
+            fork_schedule: Some(KnownChain::Helder.fork_schedule()),
+        })
+    }
+
+    #[test]
+    fn test_load_file_toml() {
+        let a = env!("CARGO_MANIFEST_DIR");
+        let mut path = PathBuf::from(a);
+
+        path.pop();
+        path.pop();
+        path.push("tests/data/helder_spec.toml");
+
+        let s = format!("chain = {path:?}");
+
+        let decoded: MockConfig = toml::from_str(&s).unwrap();
+        assert_eq!(decoded.chain, Chain::Custom {
+            genesis_time_secs: KnownChain::Helder.genesis_time_sec(),
+            slot_time_secs: KnownChain::Helder.slot_time_sec(),
+            genesis_fork_version: KnownChain::Helder.genesis_fork_version(),
+            evm_chain_id: Some(7014190335),
+            genesis_validators_root: None,
+            fork_schedule: Some(KnownChain::Helder.fork_schedule()),
         })
     }
 }
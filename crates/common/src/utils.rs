@@ -1,4 +1,10 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::{
+    fs::OpenOptions,
+    io::{ErrorKind, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use alloy::{
     primitives::U256,
@@ -6,11 +12,12 @@ use alloy::{
 };
 use axum::http::HeaderValue;
 use blst::min_pk::{PublicKey, Signature};
+use eyre::{ensure, WrapErr};
 use rand::{distributions::Alphanumeric, Rng};
 use reqwest::header::HeaderMap;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
-use tracing::Level;
+use tracing::{warn, Level};
 use tracing_appender::{non_blocking::WorkerGuard, rolling::Rotation};
 use tracing_subscriber::{fmt::Layer, prelude::*, EnvFilter};
 
@@ -23,8 +30,7 @@ use crate::{
 const MILLIS_PER_SECOND: u64 = 1_000;
 
 pub fn timestamp_of_slot_start_millis(slot: u64, chain: Chain) -> u64 {
-    let seconds_since_genesis = chain.genesis_time_sec() + slot * chain.slot_time_sec();
-    seconds_since_genesis * MILLIS_PER_SECOND
+    chain.slot_to_timestamp(slot) * MILLIS_PER_SECOND
 }
 pub fn ms_into_slot(slot: u64, chain: Chain) -> u64 {
     let slot_start_ms = timestamp_of_slot_start_millis(slot, chain);
@@ -95,12 +101,30 @@ pub mod as_str {
     }
 }
 
+/// ETH values, serialized as a decimal string. Deserializes from a number,
+/// e.g. `0.5` in the config file, or from a string as serialized
 pub mod as_eth_str {
     use alloy::primitives::U256;
     use serde::Deserialize;
 
     use super::{eth_to_wei, wei_to_eth};
 
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub(super) enum EthAmount {
+        Number(f64),
+        Str(String),
+    }
+
+    impl EthAmount {
+        pub(super) fn into_wei<E: serde::de::Error>(self) -> Result<U256, E> {
+            match self {
+                EthAmount::Number(eth) => Ok(eth_to_wei(eth)),
+                EthAmount::Str(s) => s.parse().map(eth_to_wei).map_err(E::custom),
+            }
+        }
+    }
+
     pub fn serialize<S>(data: &U256, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
@@ -113,8 +137,92 @@ pub mod as_eth_str {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = f64::deserialize(deserializer)?;
-        Ok(eth_to_wei(s))
+        EthAmount::deserialize(deserializer)?.into_wei()
+    }
+}
+
+pub mod as_opt_eth_str {
+    use alloy::primitives::U256;
+    use serde::Deserialize;
+
+    use super::as_eth_str::EthAmount;
+
+    pub fn serialize<S>(data: &Option<U256>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match data {
+            Some(data) => super::as_eth_str::serialize(data, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<U256>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Option::<EthAmount>::deserialize(deserializer)?.map(EthAmount::into_wei).transpose()
+    }
+}
+
+/// Wei values, serialized as a quoted decimal string. Since relays are not
+/// consistent in how they encode values, this deserializes from a JSON number,
+/// a quoted decimal string or a `0x`-prefixed hex string. JSON numbers above
+/// `u64::MAX` are parsed as floats, so only strings are exact at that size
+pub mod eth_value {
+    use std::fmt;
+
+    use alloy::primitives::U256;
+    use serde::de::{self, Visitor};
+
+    pub fn serialize<S>(data: &U256, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(data)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(EthValueVisitor)
+    }
+
+    struct EthValueVisitor;
+
+    impl<'de> Visitor<'de> for EthValueVisitor {
+        type Value = U256;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a number, a quoted decimal string or a 0x-prefixed hex string")
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(U256::from(v))
+        }
+
+        fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+            Ok(U256::from(v))
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            if !v.is_finite() || v < 0.0 || v.fract() != 0.0 {
+                return Err(E::custom(format!("invalid value {v}: not a whole number of wei")));
+            }
+
+            U256::from_str_radix(&format!("{v:.0}"), 10)
+                .map_err(|err| E::custom(format!("invalid value {v}: {err}")))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            let res = match v.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16),
+                None => U256::from_str_radix(v, 10),
+            };
+
+            res.map_err(|err| E::custom(format!("invalid value {v}: {err}")))
+        }
     }
 }
 
@@ -122,6 +230,14 @@ pub const fn default_u64<const U: u64>() -> u64 {
     U
 }
 
+pub const fn default_u8<const U: u8>() -> u8 {
+    U
+}
+
+pub const fn default_usize<const U: usize>() -> usize {
+    U
+}
+
 pub const fn default_bool<const U: bool>() -> bool {
     U
 }
@@ -130,6 +246,42 @@ pub const fn default_u256() -> U256 {
     U256::ZERO
 }
 
+// NETWORKING
+/// Address to bind a server to. If IPv6 is enabled this is `[::]`, which on
+/// most systems also accepts IPv4 connections (dual-stack)
+pub fn bind_address(enable_ipv6: bool, port: u16) -> SocketAddr {
+    let ip = if enable_ipv6 {
+        IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    };
+
+    SocketAddr::new(ip, port)
+}
+
+// VERSIONING
+/// Parses a version in the form `MAJOR.MINOR.PATCH`, ignoring any pre-release
+/// or build metadata suffix
+pub fn parse_semver(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(str::parse::<u64>);
+
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => Some((major, minor, patch)),
+        _ => None,
+    }
+}
+
+/// Whether `version` is compatible with `required`: the major versions have to
+/// match, and for `0.x` versions the minor versions have to match as well
+pub fn is_compatible_version(required: &str, version: &str) -> bool {
+    match (parse_semver(required), parse_semver(version)) {
+        (Some((0, required_minor, _)), Some((0, minor, _))) => required_minor == minor,
+        (Some((required_major, ..)), Some((major, ..))) => required_major == major,
+        _ => false,
+    }
+}
+
 // LOGGING
 pub fn initialize_tracing_log(module_id: &str) -> eyre::Result<WorkerGuard> {
     let settings = LogsSettings::from_env_config()?;
@@ -241,8 +393,159 @@ pub fn get_user_agent(req_headers: &HeaderMap) -> String {
         .unwrap_or_default()
 }
 
-/// Adds the commit boost version to the existing user agent
-pub fn get_user_agent_with_version(req_headers: &HeaderMap) -> eyre::Result<HeaderValue> {
+/// Adds the configured user agent, by default the commit boost version, to
+/// the existing user agent
+pub fn get_user_agent_with_version(
+    req_headers: &HeaderMap,
+    user_agent: &str,
+) -> eyre::Result<HeaderValue> {
     let ua = get_user_agent(req_headers);
-    Ok(HeaderValue::from_str(&format!("commit-boost/{HEADER_VERSION_VALUE} {}", ua))?)
+    Ok(HeaderValue::from_str(&format!("{user_agent} {ua}"))?)
+}
+
+/// User agent sent to relays, e.g. "commit-boost/0.3.0"
+pub fn default_user_agent() -> String {
+    format!("commit-boost/{HEADER_VERSION_VALUE}")
+}
+
+/// File with the PID of the current process, removed when dropped
+#[derive(Debug)]
+pub struct PidFile {
+    path: PathBuf,
+}
+
+impl PidFile {
+    /// Writes the current PID to `path`. Fails if the file already exists with
+    /// a different PID, e.g. if another instance is running
+    pub fn create(path: &Path) -> eyre::Result<Self> {
+        let pid = std::process::id();
+
+        // create_new fails if the file exists, so two instances can't both create it
+        match OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(mut file) => file
+                .write_all(pid.to_string().as_bytes())
+                .wrap_err(format!("Unable to write pid file: {}", path.display()))?,
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+                // a stale file with our pid is left over from a previous run, e.g. in a
+                // restarted container
+                let existing = std::fs::read_to_string(path).unwrap_or_default();
+                ensure!(
+                    existing.trim().parse::<u32>().ok() == Some(pid),
+                    "pid file {} already exists with pid {}, is another instance running?",
+                    path.display(),
+                    existing.trim()
+                );
+            }
+            Err(err) => {
+                return Err(err).wrap_err(format!("Unable to create pid file: {}", path.display()))
+            }
+        }
+
+        Ok(Self { path: path.to_path_buf() })
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        if let Err(err) = std::fs::remove_file(&self.path) {
+            warn!(%err, path = %self.path.display(), "failed to remove pid file");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_semver() {
+        assert_eq!(parse_semver("1.2.3"), Some((1, 2, 3)));
+        assert_eq!(parse_semver("0.1.0-rc.1"), Some((0, 1, 0)));
+        assert_eq!(parse_semver("1.2"), None);
+        assert_eq!(parse_semver("1.2.3.4"), None);
+        assert_eq!(parse_semver("v1.2.3"), None);
+    }
+
+    #[test]
+    fn test_is_compatible_version() {
+        assert!(is_compatible_version("1.2.0", "1.0.5"));
+        assert!(!is_compatible_version("1.2.0", "2.0.0"));
+        assert!(is_compatible_version("0.1.0", "0.1.3"));
+        assert!(!is_compatible_version("0.1.0", "0.2.0"));
+        assert!(!is_compatible_version("0.1.0", "invalid"));
+    }
+
+    #[test]
+    fn test_as_eth_str() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Config {
+            #[serde(with = "as_eth_str")]
+            min_bid_eth: U256,
+        }
+
+        let expected = U256::from(500_000_000_000_000_000u64);
+        for data in [r#"{"min_bid_eth": 0.5}"#, r#"{"min_bid_eth": "0.5"}"#] {
+            let config: Config = serde_json::from_str(data).unwrap();
+            assert_eq!(config.min_bid_eth, expected);
+        }
+
+        assert_eq!(
+            serde_json::to_string(&Config { min_bid_eth: expected }).unwrap(),
+            r#"{"min_bid_eth":"0.5"}"#
+        );
+        assert!(serde_json::from_str::<Config>(r#"{"min_bid_eth": "abc"}"#).is_err());
+    }
+
+    #[test]
+    fn test_eth_value() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Bid {
+            #[serde(with = "eth_value")]
+            value: U256,
+        }
+
+        let expected = U256::from(4293912964927787u64);
+        for data in [
+            r#"{"value": 4293912964927787}"#,
+            r#"{"value": "4293912964927787"}"#,
+            r#"{"value": "0xf414a84f8912b"}"#,
+        ] {
+            let bid: Bid = serde_json::from_str(data).unwrap();
+            assert_eq!(bid.value, expected);
+        }
+
+        assert_eq!(
+            serde_json::to_string(&Bid { value: expected }).unwrap(),
+            r#"{"value":"4293912964927787"}"#
+        );
+        assert!(serde_json::from_str::<Bid>(r#"{"value": "1.5"}"#).is_err());
+        assert!(serde_json::from_str::<Bid>(r#"{"value": 1.5}"#).is_err());
+
+        // above u64::MAX wei, ~18.4 ETH
+        let expected = U256::from(20u64) * U256::from(10u64).pow(U256::from(18u64));
+        for data in [
+            r#"{"value": 20000000000000000000}"#,
+            r#"{"value": "20000000000000000000"}"#,
+            r#"{"value": "0x1158e460913d00000"}"#,
+        ] {
+            let bid: Bid = serde_json::from_str(data).unwrap();
+            assert_eq!(bid.value, expected);
+        }
+    }
+
+    #[test]
+    fn test_pid_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cb.pid");
+
+        let pid_file = PidFile::create(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), std::process::id().to_string());
+        drop(pid_file);
+        assert!(!path.exists());
+
+        std::fs::write(&path, "1").unwrap();
+        assert!(PidFile::create(&path).is_err());
+    }
 }
@@ -6,3 +6,10 @@ pub(crate) const GET_HEADER_ENDPOINT_TAG: &str = "get_header";
 /// For metrics recorded when a request times out
 pub(crate) const TIMEOUT_ERROR_CODE: u16 = 555;
 pub(crate) const TIMEOUT_ERROR_CODE_STR: &str = "555";
+
+/// Number of recent slots for which submitted blocks are tracked
+pub(crate) const MAX_SUBMITTED_SLOTS: usize = 100;
+
+/// Number of builder events buffered for each events API client, a client
+/// falling further behind skips the oldest ones
+pub(crate) const EVENTS_CHANNEL_CAPACITY: usize = 256;
@@ -1,28 +1,74 @@
-use axum::{http::StatusCode, response::IntoResponse};
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use cb_common::pbs::error::PbsError;
 
 #[derive(Debug)]
 /// Errors that the PbsService returns to client
 pub enum PbsClientError {
     NoResponse,
     NoPayload,
+    /// An error from a relay or the PBS module, the status code depends on the
+    /// underlying error
+    Relay(PbsError),
+    /// The blinded block is not for the slot of the last header request
+    SlotMismatch {
+        expected: u64,
+        got: u64,
+    },
 }
 
 impl PbsClientError {
+    /// Uses the underlying [PbsError] if the report contains one, otherwise
+    /// falls back to `default`
+    pub fn from_report(err: eyre::Report, default: Self) -> Self {
+        match err.downcast::<PbsError>() {
+            Ok(err) => PbsClientError::Relay(err),
+            Err(_) => default,
+        }
+    }
+
     pub fn status_code(&self) -> StatusCode {
         match self {
             PbsClientError::NoResponse => StatusCode::SERVICE_UNAVAILABLE,
             PbsClientError::NoPayload => StatusCode::BAD_GATEWAY,
+            PbsClientError::Relay(err) => err.into_http_status(),
+            PbsClientError::SlotMismatch { .. } => StatusCode::BAD_REQUEST,
         }
     }
 }
 
 impl IntoResponse for PbsClientError {
     fn into_response(self) -> axum::response::Response {
-        let msg = match self {
-            PbsClientError::NoResponse => "no response from relays",
-            PbsClientError::NoPayload => "no payload from relays",
+        let msg = match &self {
+            PbsClientError::NoResponse => "no response from relays".to_string(),
+            PbsClientError::NoPayload => "no payload from relays".to_string(),
+            // builder API error response, see the Serialize impl of PbsError
+            PbsClientError::Relay(err) => return (self.status_code(), Json(err)).into_response(),
+            PbsClientError::SlotMismatch { expected, got } => {
+                format!("blinded block slot mismatch: expected {expected} got {got}")
+            }
         };
 
         (self.status_code(), msg).into_response()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+    use cb_common::pbs::error::PbsError;
+
+    use super::PbsClientError;
+
+    #[tokio::test]
+    async fn test_relay_error_json_body() {
+        let err = PbsError::BlockAlreadySubmitted { slot: 1 };
+        let message = err.to_string();
+        let res = PbsClientError::Relay(err).into_response();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], 409);
+        assert_eq!(body["message"], message);
+    }
+}
@@ -1,6 +1,7 @@
 mod api;
 mod constants;
 mod error;
+mod logger;
 mod metrics;
 mod mev_boost;
 mod routes;
@@ -8,6 +9,7 @@ mod service;
 mod state;
 
 pub use api::*;
+pub use logger::{RequestLogger, RequestSummary, ResponseSummary};
 pub use mev_boost::*;
 pub use service::PbsService;
 pub use state::{BuilderApiState, PbsState};
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use axum::{
+    extract::Request,
+    http::{Method, StatusCode},
+};
+use cb_common::pbs::BUILDER_API_PATH;
+
+/// Summary of a request received by the PBS module
+#[derive(Debug, Clone)]
+pub struct RequestSummary {
+    pub method: Method,
+    pub path: String,
+    /// Slot of the request, only known for `get_header`
+    pub slot: Option<u64>,
+}
+
+impl RequestSummary {
+    pub fn new(req: &Request) -> Self {
+        let path = req.uri().path().to_string();
+        let slot = path
+            .strip_prefix(BUILDER_API_PATH)
+            .and_then(|path| path.strip_prefix("/header/"))
+            .and_then(|params| params.split('/').next())
+            .and_then(|slot| slot.parse().ok());
+
+        Self { method: req.method().clone(), path, slot }
+    }
+}
+
+/// Summary of a response returned by the PBS module
+#[derive(Debug, Clone)]
+pub struct ResponseSummary {
+    pub method: Method,
+    pub path: String,
+    pub slot: Option<u64>,
+    pub status: StatusCode,
+    /// Time spent processing the request
+    pub duration: Duration,
+}
+
+/// Custom sink for request logs, e.g. to forward them to an external log
+/// pipeline. Called for all Builder API requests, in addition to the default
+/// `tracing` logs
+pub trait RequestLogger: Send + Sync {
+    fn log_request(&self, req: &RequestSummary);
+    fn log_response(&self, res: &ResponseSummary);
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, extract::Request};
+
+    use super::RequestSummary;
+
+    #[test]
+    fn test_request_summary_slot() {
+        let req = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let summary = RequestSummary::new(&req("/eth/v1/builder/header/100/0x01/0x02"));
+        assert_eq!(summary.slot, Some(100));
+        assert_eq!(summary.path, "/eth/v1/builder/header/100/0x01/0x02");
+
+        assert_eq!(RequestSummary::new(&req("/eth/v1/builder/status")).slot, None);
+    }
+}
@@ -6,7 +6,8 @@
 use lazy_static::lazy_static;
 use prometheus::{
     register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
-    register_int_gauge_vec_with_registry, HistogramVec, IntCounterVec, IntGaugeVec, Registry,
+    register_int_counter_with_registry, register_int_gauge_vec_with_registry, HistogramVec,
+    IntCounter, IntCounterVec, IntGaugeVec, Registry,
 };
 
 lazy_static! {
@@ -18,7 +19,7 @@ lazy_static! {
     pub static ref RELAY_STATUS_CODE: IntCounterVec = register_int_counter_vec_with_registry!(
         "relay_status_code_total",
         "HTTP status code received by relay",
-        &["http_status_code", "endpoint", "relay_id"],
+        &["http_status_code", "endpoint", "relay_id", "relay_set"],
         PBS_METRICS_REGISTRY
     )
     .unwrap();
@@ -27,7 +28,7 @@ lazy_static! {
     pub static ref RELAY_LATENCY: HistogramVec = register_histogram_vec_with_registry!(
         "relay_latency",
         "HTTP latency by relay",
-        &["endpoint", "relay_id"],
+        &["endpoint", "relay_id", "relay_set"],
         PBS_METRICS_REGISTRY
     )
     .unwrap();
@@ -36,7 +37,7 @@ lazy_static! {
     pub static ref RELAY_LAST_SLOT: IntGaugeVec = register_int_gauge_vec_with_registry!(
         "relay_last_slot",
         "Latest slot for which relay delivered a header",
-        &["relay_id"],
+        &["relay_id", "relay_set"],
         PBS_METRICS_REGISTRY
     )
     .unwrap();
@@ -49,4 +50,13 @@ lazy_static! {
         &["http_status_code", "endpoint"],
         PBS_METRICS_REGISTRY
     ).unwrap();
+
+    // SERVICE
+    /// Failed config reloads, the previous config is kept
+    pub static ref CONFIG_RELOAD_ERROR: IntCounter = register_int_counter_with_registry!(
+        "config_reload_error_total",
+        "Number of failed config reloads",
+        PBS_METRICS_REGISTRY
+    )
+    .unwrap();
 }
@@ -7,22 +7,29 @@ use alloy::{
 use axum::http::{HeaderMap, HeaderValue};
 use cb_common::{
     config::PbsConfig,
-    constants::APPLICATION_BUILDER_DOMAIN,
     pbs::{
+        calc_excess_blob_gas, check_consensus_version, check_json_content_type,
+        decode_relay_response,
         error::{PbsError, ValidationError},
-        GetHeaderParams, GetHeaderResponse, RelayClient, SignedExecutionPayloadHeader,
-        EMPTY_TX_ROOT_HASH, HEADER_SLOT_UUID_KEY, HEADER_START_TIME_UNIX_MS, MAX_SIZE,
+        read_body_with_max, GetHeaderParams, GetHeaderResponse, RelayClient,
+        SignedExecutionPayloadHeader, EMPTY_TX_ROOT_HASH, HEADER_SLOT_UUID_KEY,
+        HEADER_START_TIME_UNIX_MS, MAX_BLOBS_PER_BLOCK,
     },
-    signature::verify_signed_message,
+    signature::verify_signed_message_with_domain,
     types::Chain,
     utils::{get_user_agent_with_version, ms_into_slot, utcnow_ms},
 };
-use futures::future::join_all;
+use futures::{
+    future::{self, join_all},
+    stream::FuturesUnordered,
+    Stream, StreamExt,
+};
 use reqwest::{header::USER_AGENT, StatusCode};
 use tokio::time::sleep;
 use tracing::{debug, error, warn, Instrument};
 use url::Url;
 
+use super::simulate::simulated_header;
 use crate::{
     constants::{GET_HEADER_ENDPOINT_TAG, TIMEOUT_ERROR_CODE, TIMEOUT_ERROR_CODE_STR},
     metrics::{RELAY_LAST_SLOT, RELAY_LATENCY, RELAY_STATUS_CODE},
@@ -37,27 +44,30 @@ pub async fn get_header<S: BuilderApiState>(
     state: PbsState<S>,
 ) -> eyre::Result<Option<GetHeaderResponse>> {
     let ms_into_slot = ms_into_slot(params.slot, state.config.chain);
-    let max_timeout_ms = state
-        .pbs_config()
-        .timeout_get_header_ms
-        .min(state.pbs_config().late_in_slot_time_ms.saturating_sub(ms_into_slot));
+    check_slot_not_in_past(params.slot, ms_into_slot, state.config.chain)?;
+    check_slot_lookahead(params.slot, state.config.chain, state.pbs_config().max_slot_lookahead)?;
 
-    if max_timeout_ms == 0 {
+    let pbs_config = state.pbs_config();
+    if pbs_config.simulate_mode {
         warn!(
-            ms_into_slot,
-            threshold = state.pbs_config().late_in_slot_time_ms,
-            "late in slot, skipping relay requests"
+            slot = params.slot,
+            "simulate mode enabled, returning a synthetic bid with zeroed signature"
         );
+        let bid = simulated_header(params, state.config.chain, pbs_config.simulate_bid_value_gwei);
+        return Ok(state.add_bids(params.slot, vec![bid]));
+    }
 
-        return Ok(None);
+    if state.config.beacon_client.is_some() {
+        check_proposer(params, &state).map_err(PbsError::Validation)?;
     }
 
-    let (_, slot_uuid) = state.get_slot_and_uuid();
+    let max_timeout_ms = max_timeout_ms(ms_into_slot, state.pbs_config());
 
-    // prepare headers, except for start time which is set in `send_one_get_header`
-    let mut send_headers = HeaderMap::new();
-    send_headers.insert(HEADER_SLOT_UUID_KEY, HeaderValue::from_str(&slot_uuid.to_string())?);
-    send_headers.insert(USER_AGENT, get_user_agent_with_version(&req_headers)?);
+    if max_timeout_ms == 0 {
+        return Ok(None);
+    }
+
+    let send_headers = prepare_send_headers(&req_headers, &state)?;
 
     let relays = state.relays();
     let mut handles = Vec::with_capacity(relays.len());
@@ -65,7 +75,7 @@ pub async fn get_header<S: BuilderApiState>(
         handles.push(send_timed_get_header(
             params,
             relay.clone(),
-            state.config.chain,
+            state.config.builder_domain(),
             state.pbs_config(),
             send_headers.clone(),
             ms_into_slot,
@@ -73,15 +83,40 @@ pub async fn get_header<S: BuilderApiState>(
         ));
     }
 
-    let results = join_all(handles).await;
+    // the parent hash check runs alongside the relay requests, so it doesn't delay
+    // them, and it's bounded by the same timeout
+    let parent_hash_check =
+        check_parent_hash(params.parent_hash, &state, Duration::from_millis(max_timeout_ms));
+    let (results, parent) = tokio::join!(join_all(handles), parent_hash_check);
+    let registered_gas_limit = state.registered_gas_limit(&params.pubkey);
     let mut relay_bids = Vec::with_capacity(relays.len());
     for (i, res) in results.into_iter().enumerate() {
         let relay_id = relays[i].id.as_ref();
 
         match res {
             Ok(Some(res)) => {
-                RELAY_LAST_SLOT.with_label_values(&[relay_id]).set(params.slot as i64);
-                relay_bids.push(res)
+                RELAY_LAST_SLOT
+                    .with_label_values(&[relay_id, relays[i].relay_set_label()])
+                    .set(params.slot as i64);
+
+                let gas_limit = res.data.message.header.gas_limit;
+                let tolerance_bps = state.pbs_config().gas_limit_tolerance_bps;
+                let validation = validate_gas_limit(
+                    gas_limit,
+                    parent.gas_limit,
+                    registered_gas_limit,
+                    tolerance_bps,
+                )
+                .and_then(|_| match parent.blob_gas {
+                    Some((excess_blob_gas, blob_gas_used)) => {
+                        validate_excess_blob_gas(&res.data, excess_blob_gas, blob_gas_used)
+                    }
+                    None => Ok(()),
+                });
+                match validation {
+                    Ok(()) => relay_bids.push(res),
+                    Err(err) => error!(%err, relay_id, "rejecting bid"),
+                }
             }
             Ok(_) => {}
             Err(err) if err.is_timeout() => error!(err = "Timed Out", relay_id),
@@ -92,11 +127,217 @@ pub async fn get_header<S: BuilderApiState>(
     Ok(state.add_bids(params.slot, relay_bids))
 }
 
-#[tracing::instrument(skip_all, name = "handler", fields(relay_id = relay.id.as_ref()))]
+/// Like [get_header], but yields each relay's bid as soon as it arrives
+/// instead of waiting for all relays. Relays not returning a bid are skipped
+/// and dropping the stream cancels the pending requests. Bids are not added to
+/// the bid cache
+pub fn get_header_stream<'a, S: BuilderApiState>(
+    params: GetHeaderParams,
+    req_headers: &HeaderMap,
+    state: &'a PbsState<S>,
+) -> eyre::Result<impl Stream<Item = Result<GetHeaderResponse, PbsError>> + 'a> {
+    let ms_into_slot = ms_into_slot(params.slot, state.config.chain);
+    let max_timeout_ms = max_timeout_ms(ms_into_slot, state.pbs_config());
+    let send_headers = prepare_send_headers(req_headers, state)?;
+
+    let relays = if max_timeout_ms == 0 { Vec::new() } else { state.relays() };
+    let requests: FuturesUnordered<_> = relays
+        .into_iter()
+        .map(|relay| {
+            let res = send_timed_get_header(
+                params,
+                relay.clone(),
+                state.config.builder_domain(),
+                state.pbs_config(),
+                send_headers.clone(),
+                ms_into_slot,
+                max_timeout_ms,
+            );
+
+            async move {
+                let res = res.await;
+                if let Ok(Some(_)) = &res {
+                    RELAY_LAST_SLOT
+                        .with_label_values(&[relay.id.as_str(), relay.relay_set_label()])
+                        .set(params.slot as i64);
+                }
+                res
+            }
+        })
+        .collect();
+
+    Ok(requests.filter_map(|res| future::ready(res.transpose())))
+}
+
+/// Rejects requests for slots that have already passed according to the local
+/// clock, e.g. because of clock drift or a stuck beacon client
+fn check_slot_not_in_past(slot: u64, ms_into_slot: u64, chain: Chain) -> Result<(), PbsError> {
+    let current_slot = chain.current_slot();
+    if slot < current_slot {
+        let time_skew_ms = ms_into_slot.saturating_sub(chain.slot_time_sec() * 1000);
+        warn!(slot, current_slot, time_skew_ms, "get_header requested for a slot in the past");
+        return Err(PbsError::SlotInPast { slot, current_slot });
+    }
+
+    Ok(())
+}
+
+/// Rejects requests for slots too far ahead of the current slot, which are
+/// likely caused by a buggy beacon client
+fn check_slot_lookahead(slot: u64, chain: Chain, max_slot_lookahead: u8) -> Result<(), PbsError> {
+    let max_ahead = u64::from(max_slot_lookahead);
+    let current_slot = chain.current_slot();
+    if slot > current_slot + max_ahead {
+        warn!(slot, current_slot, max_ahead, "get_header requested for a slot too far ahead");
+        return Err(PbsError::SlotTooFarInFuture { slot, max_ahead });
+    }
+
+    Ok(())
+}
+
+/// Checks that the validator requesting the header is the proposer for the
+/// slot, according to the proposer duties of the consensus client. Duties are
+/// refreshed in the background, the check is skipped if they are not cached
+/// for the epoch so a slow consensus client never delays the request
+fn check_proposer<S: BuilderApiState>(
+    params: GetHeaderParams,
+    state: &PbsState<S>,
+) -> Result<(), ValidationError> {
+    let epoch = params.slot / state.config.chain.slots_per_epoch();
+    let Some(duties) = state.proposer_duties(epoch) else {
+        warn!(epoch, "proposer duties not cached, skipping proposer check");
+        return Ok(());
+    };
+
+    match duties.iter().find(|duty| duty.slot == params.slot) {
+        Some(duty) if duty.pubkey != params.pubkey => {
+            Err(ValidationError::PubkeyNotInCommittee { pubkey: params.pubkey, slot: params.slot })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that the parent hash of the requested header is a block known to the
+/// execution client, as bids must build on it. The lookup is bounded by
+/// `timeout` and a mismatch is only logged, since a freshly imported parent may
+/// not be known to the execution client yet. Returns the fields of the parent
+/// block that bids are validated against, empty if it's not found
+async fn check_parent_hash<S: BuilderApiState>(
+    parent_hash: B256,
+    state: &PbsState<S>,
+    timeout: Duration,
+) -> ParentBlockInfo {
+    let Some(execution_client) = state.config.execution_client.as_ref() else {
+        return ParentBlockInfo::default();
+    };
+
+    match tokio::time::timeout(timeout, execution_client.get_block_by_hash(parent_hash)).await {
+        Ok(Ok(Some(block))) => {
+            let blob_gas = block
+                .excess_blob_gas
+                .zip(block.blob_gas_used)
+                .map(|(excess_blob_gas, blob_gas_used)| (excess_blob_gas.to(), blob_gas_used.to()));
+            return ParentBlockInfo { gas_limit: Some(block.gas_limit.to()), blob_gas };
+        }
+        Ok(Ok(None)) => {
+            let err = ValidationError::ParentHashNotFound { parent_hash };
+            warn!(%err, "parent block unknown to the execution client, it may not be imported yet");
+        }
+        Ok(Err(err)) => {
+            warn!(%err, %parent_hash, "failed to fetch parent block, skipping parent hash check")
+        }
+        Err(_) => {
+            warn!(%parent_hash, "timed out fetching parent block, skipping parent hash check")
+        }
+    }
+
+    ParentBlockInfo::default()
+}
+
+/// Fields of the parent block fetched from the execution client
+#[derive(Debug, Default)]
+struct ParentBlockInfo {
+    gas_limit: Option<u64>,
+    /// `excess_blob_gas` and `blob_gas_used`, missing before Cancun
+    blob_gas: Option<(u64, u64)>,
+}
+
+/// Gas limit of a block building on a parent with `parent_gas_limit`, moving
+/// toward `target` by at most the step allowed by the execution layer, i.e.
+/// strictly less than 1/1024 of the parent gas limit
+fn expected_gas_limit(parent_gas_limit: u64, target: u64) -> u64 {
+    let max_step = (parent_gas_limit / 1024).saturating_sub(1);
+    if target > parent_gas_limit {
+        parent_gas_limit + max_step.min(target - parent_gas_limit)
+    } else {
+        parent_gas_limit - max_step.min(parent_gas_limit - target)
+    }
+}
+
+/// Checks that the bid gas limit is within `tolerance_bps` of the gas limit
+/// expected after moving from the parent gas limit toward the gas limit
+/// registered by the validator. Skipped if any of them is not known
+fn validate_gas_limit(
+    gas_limit: u64,
+    parent_gas_limit: Option<u64>,
+    registered_gas_limit: Option<u64>,
+    tolerance_bps: Option<u64>,
+) -> Result<(), ValidationError> {
+    let (Some(parent), Some(registered), Some(tolerance_bps)) =
+        (parent_gas_limit, registered_gas_limit, tolerance_bps)
+    else {
+        return Ok(());
+    };
+
+    let expected = expected_gas_limit(parent, registered);
+    let max_deviation = u128::from(expected) * u128::from(tolerance_bps) / 10_000;
+    if u128::from(gas_limit.abs_diff(expected)) > max_deviation {
+        return Err(ValidationError::GasLimitMismatch { registered, got: gas_limit });
+    }
+
+    Ok(())
+}
+
+/// Max time left to wait for headers, 0 if it's too late in the slot to
+/// request headers
+fn max_timeout_ms(ms_into_slot: u64, pbs_config: &PbsConfig) -> u64 {
+    let max_timeout_ms = pbs_config
+        .timeout_get_header_ms
+        .min(pbs_config.late_in_slot_time_ms.saturating_sub(ms_into_slot));
+
+    if max_timeout_ms == 0 {
+        warn!(
+            ms_into_slot,
+            threshold = pbs_config.late_in_slot_time_ms,
+            "late in slot, skipping relay requests"
+        );
+    }
+
+    max_timeout_ms
+}
+
+/// Prepare headers, except for start time which is set in `send_one_get_header`
+fn prepare_send_headers<S: BuilderApiState>(
+    req_headers: &HeaderMap,
+    state: &PbsState<S>,
+) -> eyre::Result<HeaderMap> {
+    let (_, slot_uuid) = state.get_slot_and_uuid();
+
+    let mut send_headers = HeaderMap::new();
+    send_headers.insert(HEADER_SLOT_UUID_KEY, HeaderValue::from_str(&slot_uuid.to_string())?);
+    send_headers.insert(
+        USER_AGENT,
+        get_user_agent_with_version(req_headers, &state.pbs_config().client.user_agent)?,
+    );
+
+    Ok(send_headers)
+}
+
+#[tracing::instrument(skip_all, name = "handler", fields(relay_id = relay.id.as_ref(), relay_set = relay.relay_set.as_deref().map(String::as_str)))]
 async fn send_timed_get_header(
     params: GetHeaderParams,
     relay: RelayClient,
-    chain: Chain,
+    builder_domain: [u8; 32],
     pbs_config: &PbsConfig,
     headers: HeaderMap,
     ms_into_slot: u64,
@@ -128,7 +369,7 @@ async fn send_timed_get_header(
                     send_one_get_header(
                         params,
                         relay.clone(),
-                        chain,
+                        builder_domain,
                         pbs_config.skip_sigverify,
                         pbs_config.min_bid_wei,
                         RequestConfig {
@@ -188,7 +429,7 @@ async fn send_timed_get_header(
     send_one_get_header(
         params,
         relay,
-        chain,
+        builder_domain,
         pbs_config.skip_sigverify,
         pbs_config.min_bid_wei,
         RequestConfig { timeout_ms: timeout_left_ms, url, headers },
@@ -206,7 +447,7 @@ struct RequestConfig {
 async fn send_one_get_header(
     params: GetHeaderParams,
     relay: RelayClient,
-    chain: Chain,
+    builder_domain: [u8; 32],
     skip_sigverify: bool,
     min_bid_wei: U256,
     mut req_config: RequestConfig,
@@ -229,7 +470,12 @@ async fn send_one_get_header(
         Ok(res) => res,
         Err(err) => {
             RELAY_STATUS_CODE
-                .with_label_values(&[TIMEOUT_ERROR_CODE_STR, GET_HEADER_ENDPOINT_TAG, &relay.id])
+                .with_label_values(&[
+                    TIMEOUT_ERROR_CODE_STR,
+                    GET_HEADER_ENDPOINT_TAG,
+                    &relay.id,
+                    relay.relay_set_label(),
+                ])
                 .inc();
             return Err(err.into());
         }
@@ -237,16 +483,21 @@ async fn send_one_get_header(
 
     let request_latency = start_request.elapsed();
     RELAY_LATENCY
-        .with_label_values(&[GET_HEADER_ENDPOINT_TAG, &relay.id])
+        .with_label_values(&[GET_HEADER_ENDPOINT_TAG, &relay.id, relay.relay_set_label()])
         .observe(request_latency.as_secs_f64());
 
     let code = res.status();
-    RELAY_STATUS_CODE.with_label_values(&[code.as_str(), GET_HEADER_ENDPOINT_TAG, &relay.id]).inc();
+    let res_headers = res.headers().clone();
+    RELAY_STATUS_CODE
+        .with_label_values(&[
+            code.as_str(),
+            GET_HEADER_ENDPOINT_TAG,
+            &relay.id,
+            relay.relay_set_label(),
+        ])
+        .inc();
 
-    let response_bytes = res.bytes().await?;
-    if response_bytes.len() > MAX_SIZE {
-        return Err(PbsError::PayloadTooLarge { payload_size: response_bytes.len() });
-    }
+    let response_bytes = read_body_with_max(res, relay.max_payload_size()).await?;
 
     if !code.is_success() {
         return Err(PbsError::RelayResponse {
@@ -264,7 +515,9 @@ async fn send_one_get_header(
         return Ok((start_request_time, None));
     }
 
-    let get_header_response: GetHeaderResponse = serde_json::from_slice(&response_bytes)?;
+    check_json_content_type(&res_headers)?;
+    check_consensus_version(&res_headers)?;
+    let get_header_response: GetHeaderResponse = decode_relay_response(&response_bytes)?;
 
     debug!(
         latency = ?request_latency,
@@ -275,11 +528,12 @@ async fn send_one_get_header(
 
     validate_header(
         &get_header_response.data,
-        chain,
+        builder_domain,
         relay.pubkey(),
         params.parent_hash,
         skip_sigverify,
         min_bid_wei,
+        relay.config.max_bid_wei,
     )?;
 
     Ok((start_request_time, Some(get_header_response)))
@@ -287,16 +541,29 @@ async fn send_one_get_header(
 
 fn validate_header(
     signed_header: &SignedExecutionPayloadHeader,
-    chain: Chain,
+    builder_domain: [u8; 32],
     expected_relay_pubkey: BlsPublicKey,
     parent_hash: B256,
     skip_sig_verify: bool,
     minimum_bid_wei: U256,
+    maximum_bid_wei: Option<U256>,
 ) -> Result<(), ValidationError> {
     let block_hash = signed_header.message.header.block_hash;
     let received_relay_pubkey = signed_header.message.pubkey;
     let tx_root = signed_header.message.header.transactions_root;
     let value = signed_header.message.value;
+    let gas_limit = signed_header.message.header.gas_limit;
+    let gas_used = signed_header.message.header.gas_used;
+
+    // cheap sanity check first, before any crypto
+    if gas_used > gas_limit {
+        return Err(ValidationError::GasUsedExceedsGasLimit { gas_limit, gas_used });
+    }
+
+    let blob_count = signed_header.message.blob_kzg_commitments.len();
+    if blob_count > MAX_BLOBS_PER_BLOCK {
+        return Err(ValidationError::LargeBlobCount { max: MAX_BLOBS_PER_BLOCK, got: blob_count });
+    }
 
     if block_hash == B256::ZERO {
         return Err(ValidationError::EmptyBlockhash);
@@ -317,6 +584,12 @@ fn validate_header(
         return Err(ValidationError::BidTooLow { min: minimum_bid_wei, got: value });
     }
 
+    if let Some(maximum_bid_wei) = maximum_bid_wei {
+        if value > maximum_bid_wei {
+            return Err(ValidationError::ValueTooHigh { max: maximum_bid_wei, got: value });
+        }
+    }
+
     if expected_relay_pubkey != received_relay_pubkey {
         return Err(ValidationError::PubkeyMismatch {
             expected: expected_relay_pubkey,
@@ -325,12 +598,11 @@ fn validate_header(
     }
 
     if !skip_sig_verify {
-        verify_signed_message(
-            chain,
+        verify_signed_message_with_domain(
             &received_relay_pubkey,
             &signed_header.message,
             &signed_header.signature,
-            APPLICATION_BUILDER_DOMAIN,
+            builder_domain,
         )
         .map_err(ValidationError::Sigverify)?;
     }
@@ -338,6 +610,26 @@ fn validate_header(
     Ok(())
 }
 
+/// Checks that the `excess_blob_gas` of a header follows the EIP-4844 update
+/// rule, given the parent block's `excess_blob_gas` and `blob_gas_used`
+pub fn validate_excess_blob_gas(
+    signed_header: &SignedExecutionPayloadHeader,
+    parent_excess_blob_gas: u64,
+    parent_blob_gas_used: u64,
+) -> Result<(), ValidationError> {
+    let expected = calc_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used);
+    let got = signed_header.message.header.excess_blob_gas;
+
+    if expected != got {
+        return Err(ValidationError::ExcessBlobGasOutOfRange {
+            expected: U256::from(expected),
+            got: U256::from(got),
+        });
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use alloy::{
@@ -346,12 +638,17 @@ mod tests {
     };
     use blst::min_pk;
     use cb_common::{
-        pbs::{error::ValidationError, SignedExecutionPayloadHeader, EMPTY_TX_ROOT_HASH},
+        pbs::{
+            error::ValidationError, KzgCommitment, SignedExecutionPayloadHeader,
+            EMPTY_TX_ROOT_HASH, MAX_BLOBS_PER_BLOCK, TARGET_BLOB_GAS_PER_BLOCK,
+        },
         signature::sign_builder_message,
         types::Chain,
     };
 
-    use super::validate_header;
+    use super::{
+        expected_gas_limit, validate_excess_blob_gas, validate_gas_limit, validate_header,
+    };
 
     #[test]
     fn test_validate_header() {
@@ -359,6 +656,7 @@ mod tests {
 
         let parent_hash = B256::from_slice(&[1; 32]);
         let chain = Chain::Holesky;
+        let domain = chain.builder_domain();
         let min_bid = U256::ZERO;
 
         let secret_key = min_pk::SecretKey::from_bytes(&[
@@ -369,15 +667,32 @@ mod tests {
         let pubkey = BlsPublicKey::from_slice(&secret_key.sk_to_pk().to_bytes());
 
         mock_header.message.header.transactions_root = EMPTY_TX_ROOT_HASH;
+        mock_header.message.header.gas_used = 1;
+
+        assert_eq!(
+            validate_header(
+                &mock_header,
+                domain,
+                BlsPublicKey::default(),
+                parent_hash,
+                false,
+                min_bid,
+                None
+            ),
+            Err(ValidationError::GasUsedExceedsGasLimit { gas_limit: 0, gas_used: 1 })
+        );
+
+        mock_header.message.header.gas_limit = 1;
 
         assert_eq!(
             validate_header(
                 &mock_header,
-                chain,
+                domain,
                 BlsPublicKey::default(),
                 parent_hash,
                 false,
-                min_bid
+                min_bid,
+                None
             ),
             Err(ValidationError::EmptyBlockhash)
         );
@@ -387,11 +702,12 @@ mod tests {
         assert_eq!(
             validate_header(
                 &mock_header,
-                chain,
+                domain,
                 BlsPublicKey::default(),
                 parent_hash,
                 false,
-                min_bid
+                min_bid,
+                None
             ),
             Err(ValidationError::ParentHashMismatch {
                 expected: parent_hash,
@@ -404,11 +720,12 @@ mod tests {
         assert_eq!(
             validate_header(
                 &mock_header,
-                chain,
+                domain,
                 BlsPublicKey::default(),
                 parent_hash,
                 false,
-                min_bid
+                min_bid,
+                None
             ),
             Err(ValidationError::EmptyTxRoot)
         );
@@ -418,39 +735,151 @@ mod tests {
         assert_eq!(
             validate_header(
                 &mock_header,
-                chain,
+                domain,
                 BlsPublicKey::default(),
                 parent_hash,
                 false,
-                min_bid
+                min_bid,
+                None
             ),
             Err(ValidationError::BidTooLow { min: min_bid, got: U256::ZERO })
         );
 
-        mock_header.message.value = U256::from(1);
+        mock_header.message.value = U256::from(2);
+
+        assert_eq!(
+            validate_header(
+                &mock_header,
+                domain,
+                BlsPublicKey::default(),
+                parent_hash,
+                false,
+                min_bid,
+                Some(U256::from(1))
+            ),
+            Err(ValidationError::ValueTooHigh { max: U256::from(1), got: U256::from(2) })
+        );
 
         mock_header.message.pubkey = pubkey;
 
         assert_eq!(
             validate_header(
                 &mock_header,
-                chain,
+                domain,
                 BlsPublicKey::default(),
                 parent_hash,
                 false,
-                min_bid
+                min_bid,
+                None
             ),
             Err(ValidationError::PubkeyMismatch { expected: BlsPublicKey::default(), got: pubkey })
         );
 
         assert!(matches!(
-            validate_header(&mock_header, chain, pubkey, parent_hash, false, min_bid),
+            validate_header(&mock_header, domain, pubkey, parent_hash, false, min_bid, None),
             Err(ValidationError::Sigverify(_))
         ));
-        assert!(validate_header(&mock_header, chain, pubkey, parent_hash, true, min_bid).is_ok());
+        assert!(
+            validate_header(&mock_header, domain, pubkey, parent_hash, true, min_bid, None).is_ok()
+        );
 
         mock_header.signature = sign_builder_message(chain, &secret_key, &mock_header.message);
 
-        assert!(validate_header(&mock_header, chain, pubkey, parent_hash, false, min_bid).is_ok())
+        assert!(validate_header(&mock_header, domain, pubkey, parent_hash, false, min_bid, None)
+            .is_ok())
+    }
+
+    #[test]
+    fn test_validate_header_blob_count() {
+        let mut mock_header = SignedExecutionPayloadHeader::default();
+        let domain = Chain::Holesky.builder_domain();
+        let commitments = &mut mock_header.message.blob_kzg_commitments;
+        for _ in 0..MAX_BLOBS_PER_BLOCK {
+            commitments.push(KzgCommitment([0; 48])).unwrap();
+        }
+
+        assert_eq!(
+            validate_header(
+                &mock_header,
+                domain,
+                BlsPublicKey::default(),
+                B256::ZERO,
+                false,
+                U256::ZERO,
+                None
+            ),
+            Err(ValidationError::EmptyBlockhash)
+        );
+
+        mock_header.message.blob_kzg_commitments.push(KzgCommitment([0; 48])).unwrap();
+
+        assert_eq!(
+            validate_header(
+                &mock_header,
+                domain,
+                BlsPublicKey::default(),
+                B256::ZERO,
+                false,
+                U256::ZERO,
+                None
+            ),
+            Err(ValidationError::LargeBlobCount {
+                max: MAX_BLOBS_PER_BLOCK,
+                got: MAX_BLOBS_PER_BLOCK + 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_excess_blob_gas() {
+        let mut mock_header = SignedExecutionPayloadHeader::default();
+
+        // parent below target, excess is reset
+        assert!(validate_excess_blob_gas(&mock_header, 0, TARGET_BLOB_GAS_PER_BLOCK / 2).is_ok());
+
+        mock_header.message.header.excess_blob_gas = 1;
+        assert_eq!(
+            validate_excess_blob_gas(&mock_header, 0, TARGET_BLOB_GAS_PER_BLOCK),
+            Err(ValidationError::ExcessBlobGasOutOfRange {
+                expected: U256::ZERO,
+                got: U256::from(1)
+            })
+        );
+
+        // parent above target, excess accumulates
+        mock_header.message.header.excess_blob_gas = 10 + TARGET_BLOB_GAS_PER_BLOCK;
+        assert!(validate_excess_blob_gas(&mock_header, 10, 2 * TARGET_BLOB_GAS_PER_BLOCK).is_ok());
+    }
+
+    #[test]
+    fn test_expected_gas_limit() {
+        // at most parent / 1024 - 1 toward the target
+        assert_eq!(expected_gas_limit(30_000_000, 36_000_000), 30_029_295);
+        assert_eq!(expected_gas_limit(30_000_000, 24_000_000), 29_970_705);
+        assert_eq!(expected_gas_limit(30_000_000, 30_010_000), 30_010_000);
+        assert_eq!(expected_gas_limit(30_000_000, 30_000_000), 30_000_000);
+    }
+
+    #[test]
+    fn test_validate_gas_limit() {
+        let parent = Some(30_000_000);
+        let registered = Some(36_000_000);
+
+        // skipped without a parent, a registration or a tolerance
+        assert!(validate_gas_limit(40_000_000, None, registered, Some(0)).is_ok());
+        assert!(validate_gas_limit(40_000_000, parent, None, Some(0)).is_ok());
+        assert!(validate_gas_limit(40_000_000, parent, registered, None).is_ok());
+
+        assert!(validate_gas_limit(30_029_295, parent, registered, Some(0)).is_ok());
+        assert_eq!(
+            validate_gas_limit(30_000_000, parent, registered, Some(0)),
+            Err(ValidationError::GasLimitMismatch { registered: 36_000_000, got: 30_000_000 })
+        );
+        // a bid at the registered gas limit is out of reach from the parent
+        assert_eq!(
+            validate_gas_limit(36_000_000, parent, registered, Some(100)),
+            Err(ValidationError::GasLimitMismatch { registered: 36_000_000, got: 36_000_000 })
+        );
+        assert!(validate_gas_limit(30_000_000, parent, registered, Some(100)).is_ok());
     }
 }
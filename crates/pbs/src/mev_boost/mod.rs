@@ -1,9 +1,10 @@
 mod get_header;
 mod register_validator;
+mod simulate;
 mod status;
 mod submit_block;
 
-pub use get_header::get_header;
+pub use get_header::{get_header, get_header_stream, validate_excess_blob_gas};
 pub use register_validator::register_validator;
 pub use status::get_status;
 pub use submit_block::submit_block;
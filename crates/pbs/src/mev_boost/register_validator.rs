@@ -1,15 +1,21 @@
-use std::time::{Duration, Instant};
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
 
-use alloy::rpc::types::beacon::relay::ValidatorRegistration;
+use alloy::rpc::types::beacon::{relay::ValidatorRegistration, BlsPublicKey};
 use axum::http::{HeaderMap, HeaderValue};
 use cb_common::{
-    pbs::{error::PbsError, RelayClient, HEADER_START_TIME_UNIX_MS, MAX_SIZE},
+    pbs::{
+        error::PbsError, read_body_with_max, BeaconApiClient, RelayClient, ValidatorStatus,
+        HEADER_START_TIME_UNIX_MS,
+    },
     utils::{get_user_agent_with_version, utcnow_ms},
 };
 use eyre::bail;
 use futures::future::join_all;
 use reqwest::header::USER_AGENT;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
 use crate::{
     constants::{REGISTER_VALIDATOR_ENDPOINT_TAG, TIMEOUT_ERROR_CODE_STR},
@@ -24,21 +30,54 @@ pub async fn register_validator<S: BuilderApiState>(
     req_headers: HeaderMap,
     state: PbsState<S>,
 ) -> eyre::Result<()> {
+    let n_received = registrations.len();
+
+    // until the first keymanager API fetch succeeds all registrations are kept
+    let validator_pubkeys = &state.pbs_config().validator_pubkeys;
+    let registrations = if !validator_pubkeys.is_empty() {
+        let managed: HashSet<_> = validator_pubkeys.iter().collect();
+        filter_managed_validators(registrations, |pubkey| managed.contains(pubkey))
+    } else if let Some(managed) = state.managed_pubkeys() {
+        filter_managed_validators(registrations, |pubkey| managed.contains(pubkey))
+    } else {
+        registrations
+    };
+    let registrations = match &state.config.beacon_client {
+        Some(beacon_client) => filter_active_validators(registrations, beacon_client).await,
+        None => registrations,
+    };
+    if registrations.is_empty() && n_received > 0 {
+        debug!(n_received, "no registrations left after filtering, not calling relays");
+        return Ok(());
+    }
+    state.record_registrations(&registrations);
+
+    if state.pbs_config().simulate_mode {
+        debug!(
+            n_registrations = registrations.len(),
+            "simulate mode enabled, not forwarding registrations to relays"
+        );
+        return Ok(());
+    }
+
     // prepare headers
     let mut send_headers = HeaderMap::new();
     send_headers
         .insert(HEADER_START_TIME_UNIX_MS, HeaderValue::from_str(&utcnow_ms().to_string())?);
-    send_headers.insert(USER_AGENT, get_user_agent_with_version(&req_headers)?);
+    send_headers.insert(
+        USER_AGENT,
+        get_user_agent_with_version(&req_headers, &state.pbs_config().client.user_agent)?,
+    );
 
     let relays = state.relays();
     let mut handles = Vec::with_capacity(relays.len());
-    for relay in relays {
-        handles.push(send_register_validator(
-            registrations.clone(),
-            relay,
-            send_headers.clone(),
-            state.pbs_config().timeout_register_validator_ms,
-        ));
+    for relay in relays.iter() {
+        let registrations = registrations.clone();
+        let headers = send_headers.clone();
+        let timeout_ms = state.pbs_config().timeout_register_validator_ms;
+        handles.push(relay.backoff_and_retry(REGISTER_VALIDATOR_ENDPOINT_TAG, move || {
+            send_register_validator(registrations.clone(), relay, headers.clone(), timeout_ms)
+        }));
     }
 
     // await for all so we avoid cancelling any pending registrations
@@ -50,7 +89,60 @@ pub async fn register_validator<S: BuilderApiState>(
     }
 }
 
-#[tracing::instrument(skip_all, name = "handler", fields(relay_id = relay.id.as_ref()))]
+/// Drops registrations of validators that are not active on the beacon chain.
+/// If the statuses can't be fetched all registrations are kept
+async fn filter_active_validators(
+    registrations: Vec<ValidatorRegistration>,
+    beacon_client: &BeaconApiClient,
+) -> Vec<ValidatorRegistration> {
+    let pubkeys: Vec<_> = registrations.iter().map(|r| r.message.pubkey).collect();
+    let statuses = match beacon_client.get_validator_statuses(&pubkeys).await {
+        Ok(statuses) => statuses,
+        Err(err) => {
+            warn!(%err, "failed to fetch validator statuses, skipping status check");
+            return registrations;
+        }
+    };
+
+    registrations
+        .into_iter()
+        .filter(|registration| {
+            let pubkey = registration.message.pubkey;
+            // validators unknown to the consensus client are yet to be activated
+            let status = statuses.get(&pubkey).copied().unwrap_or(ValidatorStatus::Pending);
+            if status == ValidatorStatus::Active {
+                return true;
+            }
+
+            let err = PbsError::ValidatorNotActive { pubkey, status };
+            debug!(%err, "skipping registration");
+            false
+        })
+        .collect()
+}
+
+/// Drops registrations of validators that are not managed, either from the
+/// static list or from the keymanager API
+fn filter_managed_validators(
+    registrations: Vec<ValidatorRegistration>,
+    is_managed: impl Fn(&BlsPublicKey) -> bool,
+) -> Vec<ValidatorRegistration> {
+    registrations
+        .into_iter()
+        .filter(|registration| {
+            let pubkey = registration.message.pubkey;
+            if is_managed(&pubkey) {
+                return true;
+            }
+
+            let err = PbsError::ValidatorNotManaged { pubkey };
+            debug!(%err, "skipping registration");
+            false
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip_all, name = "handler", fields(relay_id = relay.id.as_ref(), relay_set = relay.relay_set.as_deref().map(String::as_str)))]
 async fn send_register_validator(
     registrations: Vec<ValidatorRegistration>,
     relay: &RelayClient,
@@ -76,6 +168,7 @@ async fn send_register_validator(
                     TIMEOUT_ERROR_CODE_STR,
                     REGISTER_VALIDATOR_ENDPOINT_TAG,
                     &relay.id,
+                    relay.relay_set_label(),
                 ])
                 .inc();
             return Err(err.into());
@@ -83,18 +176,20 @@ async fn send_register_validator(
     };
     let request_latency = start_request.elapsed();
     RELAY_LATENCY
-        .with_label_values(&[REGISTER_VALIDATOR_ENDPOINT_TAG, &relay.id])
+        .with_label_values(&[REGISTER_VALIDATOR_ENDPOINT_TAG, &relay.id, relay.relay_set_label()])
         .observe(request_latency.as_secs_f64());
 
     let code = res.status();
     RELAY_STATUS_CODE
-        .with_label_values(&[code.as_str(), REGISTER_VALIDATOR_ENDPOINT_TAG, &relay.id])
+        .with_label_values(&[
+            code.as_str(),
+            REGISTER_VALIDATOR_ENDPOINT_TAG,
+            &relay.id,
+            relay.relay_set_label(),
+        ])
         .inc();
 
-    let response_bytes = res.bytes().await?;
-    if response_bytes.len() > MAX_SIZE {
-        return Err(PbsError::PayloadTooLarge { payload_size: response_bytes.len() });
-    }
+    let response_bytes = read_body_with_max(res, relay.max_payload_size()).await?;
     if !code.is_success() {
         let err = PbsError::RelayResponse {
             error_msg: String::from_utf8_lossy(&response_bytes).into_owned(),
@@ -0,0 +1,102 @@
+use alloy::primitives::{keccak256, U256};
+use cb_common::{
+    pbs::{
+        GetHeaderParams, GetHeaderResponse, SignedBlindedBeaconBlock, SubmitBlindedBlockResponse,
+        EMPTY_TX_ROOT_HASH,
+    },
+    types::Chain,
+    utils::timestamp_of_slot_start_millis,
+};
+
+const GWEI_TO_WEI: u64 = 1_000_000_000;
+const SIMULATED_GAS_LIMIT: u64 = 30_000_000;
+
+/// Builds a synthetic bid for `params`, used in simulate mode instead of
+/// querying the relays. The bid has no transactions and zeroed builder pubkey
+/// and signature, the block hash is derived from the parent hash and slot so
+/// it's stable across calls
+pub fn simulated_header(
+    params: GetHeaderParams,
+    chain: Chain,
+    value_gwei: u64,
+) -> GetHeaderResponse {
+    let mut hash_input = params.parent_hash.to_vec();
+    hash_input.extend_from_slice(&params.slot.to_be_bytes());
+
+    let mut response = GetHeaderResponse::default();
+    let message = &mut response.data.message;
+    message.value = U256::from(value_gwei) * U256::from(GWEI_TO_WEI);
+
+    let header = &mut message.header;
+    header.parent_hash = params.parent_hash;
+    header.block_hash = keccak256(hash_input);
+    header.gas_limit = SIMULATED_GAS_LIMIT;
+    header.timestamp = timestamp_of_slot_start_millis(params.slot, chain) / 1000;
+    header.transactions_root = EMPTY_TX_ROOT_HASH;
+
+    response
+}
+
+/// Builds the execution payload matching a block proposed with a bid from
+/// [simulated_header]
+pub fn simulated_payload(
+    signed_blinded_block: &SignedBlindedBeaconBlock,
+) -> SubmitBlindedBlockResponse {
+    let header = &signed_blinded_block.message.body.execution_payload_header;
+
+    let mut response = SubmitBlindedBlockResponse::default();
+    let payload = &mut response.data.execution_payload;
+    payload.parent_hash = header.parent_hash;
+    payload.fee_recipient = header.fee_recipient;
+    payload.state_root = header.state_root;
+    payload.receipts_root = header.receipts_root;
+    payload.logs_bloom = header.logs_bloom.clone();
+    payload.prev_randao = header.prev_randao;
+    payload.block_number = header.block_number;
+    payload.gas_limit = header.gas_limit;
+    payload.gas_used = header.gas_used;
+    payload.timestamp = header.timestamp;
+    payload.extra_data = header.extra_data.clone();
+    payload.base_fee_per_gas = header.base_fee_per_gas;
+    payload.block_hash = header.block_hash;
+    payload.blob_gas_used = header.blob_gas_used;
+    payload.excess_blob_gas = header.excess_blob_gas;
+    response.data.blobs_bundle = Some(Default::default());
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{B256, U256};
+    use cb_common::{
+        pbs::{GetHeaderParams, SignedBlindedBeaconBlock},
+        types::Chain,
+    };
+
+    use super::{simulated_header, simulated_payload};
+
+    #[test]
+    fn test_simulated_bid_roundtrip() {
+        let params = GetHeaderParams {
+            slot: 100,
+            parent_hash: B256::repeat_byte(1),
+            pubkey: Default::default(),
+        };
+
+        let bid = simulated_header(params, Chain::Holesky, 1_000_000);
+        assert_eq!(bid.value(), U256::from(1_000_000_000_000_000u64));
+        assert_eq!(bid.data.message.header.parent_hash, params.parent_hash);
+        assert_ne!(bid.block_hash(), B256::ZERO);
+        assert_eq!(bid.block_hash(), simulated_header(params, Chain::Holesky, 1).block_hash());
+
+        let mut block = SignedBlindedBeaconBlock::default();
+        block.message.slot = params.slot;
+        block.message.body.execution_payload_header = bid.data.message.header.clone();
+
+        let payload = simulated_payload(&block);
+        assert_eq!(payload.block_hash(), bid.block_hash());
+        assert_eq!(payload.data.execution_payload.parent_hash, params.parent_hash);
+        assert_eq!(payload.data.execution_payload.timestamp, bid.data.message.header.timestamp);
+    }
+}
@@ -2,7 +2,7 @@ use std::time::{Duration, Instant};
 
 use axum::http::HeaderMap;
 use cb_common::{
-    pbs::{error::PbsError, RelayClient, MAX_SIZE},
+    pbs::{error::PbsError, read_body_with_max, RelayClient},
     utils::get_user_agent_with_version,
 };
 use futures::future::select_ok;
@@ -22,18 +22,24 @@ pub async fn get_status<S: BuilderApiState>(
     req_headers: HeaderMap,
     state: PbsState<S>,
 ) -> eyre::Result<()> {
-    // If no relay check, return early
-    if !state.config.pbs_config.relay_check {
+    // If no relay check, or no relay to check in simulate mode, return early
+    if !state.config.pbs_config.relay_check || state.pbs_config().simulate_mode {
         Ok(())
     } else {
         // prepare headers
         let mut send_headers = HeaderMap::new();
-        send_headers.insert(USER_AGENT, get_user_agent_with_version(&req_headers)?);
+        send_headers.insert(
+            USER_AGENT,
+            get_user_agent_with_version(&req_headers, &state.pbs_config().client.user_agent)?,
+        );
 
         let relays = state.relays();
         let mut handles = Vec::with_capacity(relays.len());
-        for relay in relays {
-            handles.push(Box::pin(send_relay_check(relay, send_headers.clone())));
+        for relay in relays.iter() {
+            let headers = send_headers.clone();
+            handles.push(Box::pin(relay.backoff_and_retry(STATUS_ENDPOINT_TAG, move || {
+                send_relay_check(relay, headers.clone())
+            })));
         }
 
         // return ok if at least one relay returns 200
@@ -45,7 +51,7 @@ pub async fn get_status<S: BuilderApiState>(
     }
 }
 
-#[tracing::instrument(skip_all, name = "handler", fields(relay_id = relay.id.as_ref()))]
+#[tracing::instrument(skip_all, name = "handler", fields(relay_id = relay.id.as_ref(), relay_set = relay.relay_set.as_deref().map(String::as_str)))]
 async fn send_relay_check(relay: &RelayClient, headers: HeaderMap) -> Result<(), PbsError> {
     let url = relay.get_status_url()?;
 
@@ -61,23 +67,32 @@ async fn send_relay_check(relay: &RelayClient, headers: HeaderMap) -> Result<(),
         Ok(res) => res,
         Err(err) => {
             RELAY_STATUS_CODE
-                .with_label_values(&[TIMEOUT_ERROR_CODE_STR, STATUS_ENDPOINT_TAG, &relay.id])
+                .with_label_values(&[
+                    TIMEOUT_ERROR_CODE_STR,
+                    STATUS_ENDPOINT_TAG,
+                    &relay.id,
+                    relay.relay_set_label(),
+                ])
                 .inc();
             return Err(err.into());
         }
     };
     let request_latency = start_request.elapsed();
     RELAY_LATENCY
-        .with_label_values(&[STATUS_ENDPOINT_TAG, &relay.id])
+        .with_label_values(&[STATUS_ENDPOINT_TAG, &relay.id, relay.relay_set_label()])
         .observe(request_latency.as_secs_f64());
 
     let code = res.status();
-    RELAY_STATUS_CODE.with_label_values(&[code.as_str(), STATUS_ENDPOINT_TAG, &relay.id]).inc();
+    RELAY_STATUS_CODE
+        .with_label_values(&[
+            code.as_str(),
+            STATUS_ENDPOINT_TAG,
+            &relay.id,
+            relay.relay_set_label(),
+        ])
+        .inc();
 
-    let response_bytes = res.bytes().await?;
-    if response_bytes.len() > MAX_SIZE {
-        return Err(PbsError::PayloadTooLarge { payload_size: response_bytes.len() });
-    }
+    let response_bytes = read_body_with_max(res, relay.max_payload_size()).await?;
     if !code.is_success() {
         let err = PbsError::RelayResponse {
             error_msg: String::from_utf8_lossy(&response_bytes).into_owned(),
@@ -3,16 +3,19 @@ use std::time::{Duration, Instant};
 use axum::http::{HeaderMap, HeaderValue};
 use cb_common::{
     pbs::{
+        check_consensus_version, check_json_content_type, decode_relay_response,
         error::{PbsError, ValidationError},
-        RelayClient, SignedBlindedBeaconBlock, SubmitBlindedBlockResponse, HEADER_SLOT_UUID_KEY,
-        HEADER_START_TIME_UNIX_MS, MAX_SIZE,
+        read_body_with_max, RelayClient, SignedBlindedBeaconBlock, SubmitBlindedBlockResponse,
+        HEADER_SLOT_UUID_KEY, HEADER_START_TIME_UNIX_MS, MAX_BLOBS_PER_BLOCK,
     },
+    types::Chain,
     utils::{get_user_agent_with_version, utcnow_ms},
 };
 use futures::future::select_ok;
 use reqwest::header::USER_AGENT;
 use tracing::{debug, warn};
 
+use super::simulate::simulated_payload;
 use crate::{
     constants::{SUBMIT_BLINDED_BLOCK_ENDPOINT_TAG, TIMEOUT_ERROR_CODE_STR},
     metrics::{RELAY_LATENCY, RELAY_STATUS_CODE},
@@ -25,13 +28,21 @@ pub async fn submit_block<S: BuilderApiState>(
     req_headers: HeaderMap,
     state: PbsState<S>,
 ) -> eyre::Result<SubmitBlindedBlockResponse> {
+    if state.pbs_config().simulate_mode {
+        warn!("simulate mode enabled, returning a synthetic payload");
+        return Ok(simulated_payload(&signed_blinded_block));
+    }
+
     let (_, slot_uuid) = state.get_slot_and_uuid();
 
     // prepare headers
     let mut send_headers = HeaderMap::new();
     send_headers.insert(HEADER_SLOT_UUID_KEY, HeaderValue::from_str(&slot_uuid.to_string())?);
     send_headers.insert(HEADER_START_TIME_UNIX_MS, HeaderValue::from(utcnow_ms()));
-    send_headers.insert(USER_AGENT, get_user_agent_with_version(&req_headers)?);
+    send_headers.insert(
+        USER_AGENT,
+        get_user_agent_with_version(&req_headers, &state.pbs_config().client.user_agent)?,
+    );
 
     let relays = state.relays();
     let mut handles = Vec::with_capacity(relays.len());
@@ -41,6 +52,7 @@ pub async fn submit_block<S: BuilderApiState>(
             relay,
             send_headers.clone(),
             state.config.pbs_config.timeout_get_payload_ms,
+            state.config.chain,
         )));
     }
 
@@ -53,12 +65,13 @@ pub async fn submit_block<S: BuilderApiState>(
 
 // submits blinded signed block and expects the execution payload + blobs bundle
 // back
-#[tracing::instrument(skip_all, name = "handler", fields(relay_id = relay.id.as_ref()))]
+#[tracing::instrument(skip_all, name = "handler", fields(relay_id = relay.id.as_ref(), relay_set = relay.relay_set.as_deref().map(String::as_str)))]
 async fn send_submit_block(
     signed_blinded_block: &SignedBlindedBeaconBlock,
     relay: &RelayClient,
     headers: HeaderMap,
     timeout_ms: u64,
+    chain: Chain,
 ) -> Result<SubmitBlindedBlockResponse, PbsError> {
     let url = relay.submit_block_url()?;
 
@@ -79,6 +92,7 @@ async fn send_submit_block(
                     TIMEOUT_ERROR_CODE_STR,
                     SUBMIT_BLINDED_BLOCK_ENDPOINT_TAG,
                     &relay.id,
+                    relay.relay_set_label(),
                 ])
                 .inc();
             return Err(err.into());
@@ -86,19 +100,21 @@ async fn send_submit_block(
     };
     let request_latency = start_request.elapsed();
     RELAY_LATENCY
-        .with_label_values(&[SUBMIT_BLINDED_BLOCK_ENDPOINT_TAG, &relay.id])
+        .with_label_values(&[SUBMIT_BLINDED_BLOCK_ENDPOINT_TAG, &relay.id, relay.relay_set_label()])
         .observe(request_latency.as_secs_f64());
 
     let code = res.status();
+    let res_headers = res.headers().clone();
     RELAY_STATUS_CODE
-        .with_label_values(&[code.as_str(), SUBMIT_BLINDED_BLOCK_ENDPOINT_TAG, &relay.id])
+        .with_label_values(&[
+            code.as_str(),
+            SUBMIT_BLINDED_BLOCK_ENDPOINT_TAG,
+            &relay.id,
+            relay.relay_set_label(),
+        ])
         .inc();
 
-    let response_bytes = res.bytes().await?;
-
-    if response_bytes.len() > MAX_SIZE {
-        return Err(PbsError::PayloadTooLarge { payload_size: response_bytes.len() });
-    }
+    let response_bytes = read_body_with_max(res, relay.max_payload_size()).await?;
     if !code.is_success() {
         let err = PbsError::RelayResponse {
             error_msg: String::from_utf8_lossy(&response_bytes).into_owned(),
@@ -110,7 +126,9 @@ async fn send_submit_block(
         return Err(err);
     };
 
-    let block_response: SubmitBlindedBlockResponse = serde_json::from_slice(&response_bytes)?;
+    check_json_content_type(&res_headers)?;
+    check_consensus_version(&res_headers)?;
+    let block_response: SubmitBlindedBlockResponse = decode_relay_response(&response_bytes)?;
 
     debug!(
         latency = ?request_latency,
@@ -118,6 +136,8 @@ async fn send_submit_block(
         "received unblinded block"
     );
 
+    validate_payload_slot(signed_blinded_block, &block_response, chain)?;
+
     if signed_blinded_block.block_hash() != block_response.block_hash() {
         return Err(PbsError::Validation(ValidationError::BlockHashMismatch {
             expected: signed_blinded_block.block_hash(),
@@ -126,6 +146,13 @@ async fn send_submit_block(
     }
 
     if let Some(blobs) = &block_response.data.blobs_bundle {
+        if blobs.blobs.len() > MAX_BLOBS_PER_BLOCK {
+            return Err(PbsError::Validation(ValidationError::LargeBlobCount {
+                max: MAX_BLOBS_PER_BLOCK,
+                got: blobs.blobs.len(),
+            }));
+        }
+
         let expected_committments = &signed_blinded_block.message.body.blob_kzg_commitments;
         if expected_committments.len() != blobs.blobs.len() ||
             expected_committments.len() != blobs.commitments.len() ||
@@ -153,3 +180,48 @@ async fn send_submit_block(
 
     Ok(block_response)
 }
+
+/// Checks that the payload timestamp is the start of the slot of the
+/// submitted block
+fn validate_payload_slot(
+    signed_blinded_block: &SignedBlindedBeaconBlock,
+    block_response: &SubmitBlindedBlockResponse,
+    chain: Chain,
+) -> Result<(), ValidationError> {
+    let expected = signed_blinded_block.message.slot;
+    let timestamp = block_response.data.execution_payload.timestamp;
+
+    if timestamp != chain.slot_to_timestamp(expected) {
+        let got = timestamp.saturating_sub(chain.genesis_time_sec()) / chain.slot_time_sec();
+        return Err(ValidationError::SlotMismatch { expected, got });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use cb_common::{
+        pbs::{error::ValidationError, SignedBlindedBeaconBlock, SubmitBlindedBlockResponse},
+        types::Chain,
+    };
+
+    use super::validate_payload_slot;
+
+    #[test]
+    fn test_validate_payload_slot() {
+        let chain = Chain::Holesky;
+        let mut block = SignedBlindedBeaconBlock::default();
+        block.message.slot = 100;
+
+        let mut response = SubmitBlindedBlockResponse::default();
+        response.data.execution_payload.timestamp = chain.slot_to_timestamp(100);
+        assert!(validate_payload_slot(&block, &response, chain).is_ok());
+
+        response.data.execution_payload.timestamp = chain.slot_to_timestamp(99);
+        assert_eq!(
+            validate_payload_slot(&block, &response, chain),
+            Err(ValidationError::SlotMismatch { expected: 100, got: 99 })
+        );
+    }
+}
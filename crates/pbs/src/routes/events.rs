@@ -0,0 +1,50 @@
+use axum::{
+    extract::State,
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+};
+use futures::stream;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, warn};
+
+use crate::state::{BuilderApiState, PbsState};
+
+/// Streams the builder events to clients authenticated with the events API
+/// JWT, as server-sent events with a JSON event each
+pub async fn handle_events<S: BuilderApiState>(
+    State(state): State<PbsState<S>>,
+    req_headers: HeaderMap,
+) -> Response {
+    let (Some(jwt), Some(receiver)) =
+        (state.config.events_api_jwt.as_ref(), state.subscribe_events())
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let bearer = req_headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if bearer != Some(jwt.as_str()) {
+        warn!("unauthorized events API request");
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    debug!("events API client connected");
+    let events = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((Event::default().json_data(event), receiver)),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "events API client is lagging, skipping events")
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default()).into_response()
+}
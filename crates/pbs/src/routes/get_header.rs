@@ -54,7 +54,7 @@ pub async fn handle_get_header<S: BuilderApiState, A: BuilderApi<S>>(
         Err(err) => {
             error!(%err, "no header available from relays");
 
-            let err = PbsClientError::NoPayload;
+            let err = PbsClientError::from_report(err, PbsClientError::NoPayload);
             BEACON_NODE_STATUS
                 .with_label_values(&[err.status_code().as_str(), GET_HEADER_ENDPOINT_TAG])
                 .inc();
@@ -0,0 +1,26 @@
+use axum::{extract::State, Json};
+use cb_common::pbs::{HealthResponse, StatsResponse, HEADER_VERSION_VALUE};
+
+use crate::state::{BuilderApiState, PbsState};
+
+pub async fn handle_health<S: BuilderApiState>(
+    State(state): State<PbsState<S>>,
+) -> Json<HealthResponse> {
+    let uptime_secs = state.config.uptime_start.elapsed().unwrap_or_default().as_secs();
+
+    Json(HealthResponse {
+        uptime_secs,
+        version: HEADER_VERSION_VALUE.to_string(),
+        chain: state.config.chain.to_string(),
+    })
+}
+
+pub async fn handle_stats<S: BuilderApiState>(
+    State(state): State<PbsState<S>>,
+) -> Json<StatsResponse> {
+    Json(StatsResponse {
+        requests: state.request_count(),
+        errors: state.error_count(),
+        uptime_secs: state.config.uptime_start.elapsed().unwrap_or_default().as_secs(),
+    })
+}
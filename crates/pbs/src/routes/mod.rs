@@ -1,10 +1,14 @@
+mod events;
 mod get_header;
+mod health;
 mod register_validator;
 mod router;
 mod status;
 mod submit_block;
 
+use events::handle_events;
 use get_header::handle_get_header;
+use health::{handle_health, handle_stats};
 use register_validator::handle_register_validator;
 pub use router::create_app_router;
 use status::handle_get_status;
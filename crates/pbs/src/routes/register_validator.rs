@@ -43,7 +43,7 @@ pub async fn handle_register_validator<S: BuilderApiState, A: BuilderApi<S>>(
         state.publish_event(BuilderEvent::RegisterValidatorResponse);
         error!(%err, "all relays failed registration");
 
-        let err = PbsClientError::NoResponse;
+        let err = PbsClientError::from_report(err, PbsClientError::NoResponse);
         BEACON_NODE_STATUS
             .with_label_values(&[err.status_code().as_str(), REGISTER_VALIDATOR_ENDPOINT_TAG])
             .inc();
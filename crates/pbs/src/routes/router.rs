@@ -1,14 +1,26 @@
+use std::time::Instant;
+
 use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Router,
 };
 use cb_common::pbs::{
-    BUILDER_API_PATH, GET_HEADER_PATH, GET_STATUS_PATH, REGISTER_VALIDATOR_PATH, SUBMIT_BLOCK_PATH,
+    BUILDER_API_PATH, EVENTS_PATH, GET_HEADER_PATH, GET_STATUS_PATH, HEALTH_PATH,
+    REGISTER_VALIDATOR_PATH, STATS_PATH, SUBMIT_BLOCK_PATH,
 };
+use tracing::warn;
 
-use super::{handle_get_header, handle_get_status, handle_register_validator, handle_submit_block};
+use super::{
+    handle_events, handle_get_header, handle_get_status, handle_health, handle_register_validator,
+    handle_stats, handle_submit_block,
+};
 use crate::{
     api::BuilderApi,
+    logger::{RequestSummary, ResponseSummary},
     state::{BuilderApiState, PbsState},
 };
 
@@ -27,5 +39,73 @@ pub fn create_app_router<S: BuilderApiState, A: BuilderApi<S>>(state: PbsState<S
         builder_api
     };
 
+    // admin endpoints are not subject to backpressure and are not counted
+    let app = app
+        .layer(middleware::from_fn_with_state(state.clone(), limit_in_flight::<S>))
+        .layer(middleware::from_fn_with_state(state.clone(), count_requests::<S>))
+        .layer(middleware::from_fn_with_state(state.clone(), log_requests::<S>))
+        .route(HEALTH_PATH, get(handle_health::<S>))
+        .route(STATS_PATH, get(handle_stats::<S>));
+
+    // neither are the long-lived events API connections
+    let app = if state.config.events_api_jwt.is_some() {
+        app.route(EVENTS_PATH, get(handle_events::<S>))
+    } else {
+        app
+    };
+
     app.with_state(state)
 }
+
+async fn count_requests<S: BuilderApiState>(
+    State(state): State<PbsState<S>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let res = next.run(req).await;
+    let status = res.status();
+    state.record_request(status.is_client_error() || status.is_server_error());
+    res
+}
+
+async fn log_requests<S: BuilderApiState>(
+    State(state): State<PbsState<S>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(logger) = state.request_logger() else {
+        return next.run(req).await;
+    };
+
+    let summary = RequestSummary::new(&req);
+    logger.log_request(&summary);
+
+    let start = Instant::now();
+    let res = next.run(req).await;
+    logger.log_response(&ResponseSummary {
+        method: summary.method,
+        path: summary.path,
+        slot: summary.slot,
+        status: res.status(),
+        duration: start.elapsed(),
+    });
+    res
+}
+
+async fn limit_in_flight<S: BuilderApiState>(
+    State(state): State<PbsState<S>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(limit) = state.in_flight_limit() else {
+        return next.run(req).await;
+    };
+
+    match limit.try_acquire() {
+        Ok(_permit) => next.run(req).await,
+        Err(_) => {
+            warn!(path = req.uri().path(), "too many requests in flight, rejecting");
+            StatusCode::SERVICE_UNAVAILABLE.into_response()
+        }
+    }
+}
@@ -34,7 +34,7 @@ pub async fn handle_get_status<S: BuilderApiState, A: BuilderApi<S>>(
         Err(err) => {
             error!(%err, "all relays failed get_status");
 
-            let err = PbsClientError::NoResponse;
+            let err = PbsClientError::from_report(err, PbsClientError::NoResponse);
             BEACON_NODE_STATUS
                 .with_label_values(&[err.status_code().as_str(), STATUS_ENDPOINT_TAG])
                 .inc();
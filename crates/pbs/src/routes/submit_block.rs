@@ -1,6 +1,6 @@
 use axum::{extract::State, http::HeaderMap, response::IntoResponse, Json};
 use cb_common::{
-    pbs::{BuilderEvent, SignedBlindedBeaconBlock},
+    pbs::{error::PbsError, BuilderEvent, SignedBlindedBeaconBlock},
     utils::{get_user_agent, timestamp_of_slot_start_millis, utcnow_ms},
 };
 use reqwest::StatusCode;
@@ -34,7 +34,25 @@ pub async fn handle_submit_block<S: BuilderApiState, A: BuilderApi<S>>(
     info!(ua, %slot_uuid, ms_into_slot=now.saturating_sub(slot_start_ms), %block_hash);
 
     if curr_slot != signed_blinded_block.message.slot {
-        warn!(expected = curr_slot, got = slot, "blinded beacon slot mismatch")
+        warn!(expected = curr_slot, got = slot, "blinded beacon slot mismatch");
+
+        if state.config.pbs_global.strict_mode {
+            let err = PbsClientError::SlotMismatch { expected: curr_slot, got: slot };
+            BEACON_NODE_STATUS
+                .with_label_values(&[err.status_code().as_str(), SUBMIT_BLINDED_BLOCK_ENDPOINT_TAG])
+                .inc();
+            return Err(err);
+        }
+    }
+
+    if !state.try_mark_slot_submitted(slot) {
+        error!("CRITICAL: blinded block already submitted for this slot, refusing to submit again");
+
+        let err = PbsClientError::Relay(PbsError::BlockAlreadySubmitted { slot });
+        BEACON_NODE_STATUS
+            .with_label_values(&[err.status_code().as_str(), SUBMIT_BLINDED_BLOCK_ENDPOINT_TAG])
+            .inc();
+        return Err(err);
     }
 
     match A::submit_block(signed_blinded_block, req_headers, state.clone()).await {
@@ -48,6 +66,9 @@ pub async fn handle_submit_block<S: BuilderApiState, A: BuilderApi<S>>(
         }
 
         Err(err) => {
+            // no relay returned the payload, so a retry can't be a double submission
+            state.unmark_slot_submitted(slot);
+
             if let Some(fault_pubkeys) = state.get_relays_by_block_hash(slot, block_hash) {
                 let fault_relays = state
                     .relays()
@@ -70,7 +91,7 @@ pub async fn handle_submit_block<S: BuilderApiState, A: BuilderApi<S>>(
                 });
             };
 
-            let err = PbsClientError::NoPayload;
+            let err = PbsClientError::from_report(err, PbsClientError::NoPayload);
             BEACON_NODE_STATUS
                 .with_label_values(&[err.status_code().as_str(), SUBMIT_BLINDED_BLOCK_ENDPOINT_TAG])
                 .inc();
@@ -1,14 +1,22 @@
-use std::net::SocketAddr;
+use std::time::Duration;
 
+use cb_common::{
+    config::HealthCheckOnStartup,
+    pbs::{error::PbsError, BeaconApiClient, Heartbeat, KeymanagerClient},
+    types::{Chain, ChainDetection, KnownChain},
+    utils::{bind_address, utcnow_sec, PidFile},
+    DEFAULT_REQUEST_TIMEOUT,
+};
 use cb_metrics::provider::MetricsProvider;
 use eyre::{Context, Result};
 use prometheus::core::Collector;
 use tokio::net::TcpListener;
-use tracing::info;
+use tracing::{debug, error, info, warn};
+use url::Url;
 
 use crate::{
     api::BuilderApi,
-    metrics::PBS_METRICS_REGISTRY,
+    metrics::{CONFIG_RELOAD_ERROR, PBS_METRICS_REGISTRY},
     routes::create_app_router,
     state::{BuilderApiState, PbsState},
 };
@@ -19,15 +27,67 @@ pub struct PbsService;
 
 impl PbsService {
     pub async fn run<S: BuilderApiState, A: BuilderApi<S>>(state: PbsState<S>) -> Result<()> {
-        let address = SocketAddr::from(([0, 0, 0, 0], state.config.pbs_config.port));
+        let address = bind_address(state.config.enable_ipv6, state.config.pbs_config.port);
         let events_subs =
             state.config.event_publiher.as_ref().map(|e| e.n_subscribers()).unwrap_or_default();
-        info!(?address, events_subs, chain =? state.config.chain, "Starting PBS service");
+        info!(
+            ?address,
+            events_subs,
+            chain =? state.config.chain,
+            relay_set = state.config.pbs_global.relay_set_name.as_deref(),
+            "Starting PBS service"
+        );
+
+        if state.pbs_config().simulate_mode {
+            warn!("simulate mode enabled, relays will not be contacted and bids are synthetic");
+        }
+
+        // removed when the service stops
+        let _pid_file =
+            state.config.pbs_global.pid_file.as_deref().map(PidFile::create).transpose()?;
+
+        let health_check = state.config.startup_health_check();
+        if let Some(beacon_client) = &state.config.beacon_client {
+            if health_check != HealthCheckOnStartup::Skip {
+                check_chain(beacon_client, state.config.chain, health_check).await?;
+            }
+        }
+
+        if state.config.pbs_global.relay_list_file.is_some() {
+            let relays = state.config.load_relays_with_list_file()?;
+            info!(n_relays = relays.len(), "loaded relays with relay list file");
+            state.set_relays(relays);
+
+            #[cfg(unix)]
+            tokio::spawn(reload_relay_list_on_sighup(state.clone()));
+        }
+
+        if let Some(beacon_client) = state.config.beacon_client.clone() {
+            tokio::spawn(refresh_proposer_duties(state.clone(), beacon_client));
+        }
+
+        if let Some(url) = state.pbs_config().relay_exclusion_list_url.clone() {
+            let refresh = Duration::from_secs(state.pbs_config().relay_exclusion_list_refresh_secs);
+            tokio::spawn(refresh_relay_exclusion_list(state.clone(), url, refresh));
+        }
+
+        if let Some(keymanager_client) = state.config.keymanager_client.clone() {
+            let refresh = Duration::from_secs(state.config.pbs_global.keymanager_refresh_secs);
+            tokio::spawn(refresh_managed_pubkeys(state.clone(), keymanager_client, refresh));
+        }
+
+        if let Some(url) = state.config.pbs_global.heartbeat_url.clone() {
+            let interval = Duration::from_secs(state.config.pbs_global.heartbeat_interval_secs);
+            tokio::spawn(send_heartbeats(state.config.chain, url, interval));
+        }
 
         let app = create_app_router::<S, A>(state);
         let listener = TcpListener::bind(address).await.expect("failed tcp binding");
 
-        axum::serve(listener, app).await.wrap_err("PBS server exited")
+        axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown_signal())
+            .await
+            .wrap_err("PBS server exited")
     }
 
     pub fn register_metric(c: Box<dyn Collector>) {
@@ -38,3 +98,220 @@ impl PbsService {
         MetricsProvider::load_and_run(PBS_METRICS_REGISTRY.clone())
     }
 }
+
+/// Resolves when the process receives SIGINT or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            error!(%err, "failed to listen for ctrl-c");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                error!(%err, "failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    info!("Shutting down PBS service");
+}
+
+/// Reloads the relay list file on SIGHUP. If the file can't be loaded the
+/// current relays are kept
+#[cfg(unix)]
+async fn reload_relay_list_on_sighup<S: BuilderApiState>(state: PbsState<S>) {
+    let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+        Ok(signal) => signal,
+        Err(err) => {
+            error!(%err, "failed to listen for SIGHUP, relay list file won't be reloaded");
+            return;
+        }
+    };
+
+    while hangup.recv().await.is_some() {
+        match state.config.load_relays_with_list_file() {
+            Ok(relays) => {
+                info!(n_relays = relays.len(), "reloaded relay list file");
+                state.set_relays(relays);
+            }
+            Err(err) => {
+                CONFIG_RELOAD_ERROR.inc();
+                let err = PbsError::ConfigReloadFailed(format!("{err:#}"));
+                error!(%err, "failed to reload relay list file, keeping current relays");
+            }
+        }
+    }
+}
+
+/// Verifies that the consensus client is on the configured chain. A mismatch,
+/// or failing to reach the consensus client, prevents the service from
+/// starting only with a strict health check
+async fn check_chain(
+    beacon_client: &BeaconApiClient,
+    chain: Chain,
+    health_check: HealthCheckOnStartup,
+) -> Result<()> {
+    let strict = health_check == HealthCheckOnStartup::Strict;
+
+    let genesis = match beacon_client.get_genesis().await {
+        Ok(genesis) => genesis,
+        Err(err) if strict => {
+            return Err(err).wrap_err("failed to fetch genesis from consensus client");
+        }
+        Err(err) => {
+            warn!(?err, "failed to fetch genesis from consensus client, skipping chain check");
+            return Ok(());
+        }
+    };
+
+    if genesis.genesis_fork_version == chain.genesis_fork_version() {
+        return Ok(());
+    }
+
+    let detected = match KnownChain::detect(genesis.genesis_fork_version) {
+        ChainDetection::Known(known) => Chain::from(known),
+        ChainDetection::Unknown(genesis_fork_version) => Chain::Custom {
+            genesis_time_secs: genesis.genesis_time,
+            slot_time_secs: chain.slot_time_sec(),
+            genesis_fork_version,
+            evm_chain_id: None,
+            genesis_validators_root: Some(genesis.genesis_validators_root.0),
+            fork_schedule: None,
+        },
+    };
+
+    let err = PbsError::ChainMismatch { configured: chain, detected };
+    if strict {
+        return Err(err.into());
+    }
+
+    error!(%err, "consensus client is on a different chain than configured");
+    Ok(())
+}
+
+/// Periodically fetches the relay exclusion list, a JSON array of relay urls.
+/// If fetching fails the previous list is kept
+async fn refresh_relay_exclusion_list<S: BuilderApiState>(
+    state: PbsState<S>,
+    url: Url,
+    refresh: Duration,
+) {
+    let client = reqwest::Client::new();
+    let mut interval = tokio::time::interval(refresh);
+
+    loop {
+        interval.tick().await;
+
+        match fetch_relay_exclusion_list(&client, url.clone()).await {
+            Ok(excluded) => {
+                let total = state.relay_count();
+                state.set_excluded_relays(&excluded);
+                let n_excluded = total.saturating_sub(state.relays().len());
+
+                if n_excluded > 0 {
+                    warn!(n_excluded, total, "excluding relays from the exclusion list");
+                } else {
+                    debug!(list_len = excluded.len(), "no configured relay is excluded");
+                }
+            }
+            Err(err) => warn!(%err, %url, "failed to fetch relay exclusion list"),
+        }
+    }
+}
+
+async fn fetch_relay_exclusion_list(client: &reqwest::Client, url: Url) -> Result<Vec<Url>> {
+    let res = client.get(url).timeout(DEFAULT_REQUEST_TIMEOUT).send().await?.error_for_status()?;
+    Ok(res.json().await?)
+}
+
+/// Fetches the proposer duties of the current and next epoch every slot, if not
+/// cached yet, so get_header doesn't wait for the consensus client
+async fn refresh_proposer_duties<S: BuilderApiState>(
+    state: PbsState<S>,
+    beacon_client: BeaconApiClient,
+) {
+    let chain = state.config.chain;
+    let mut interval = tokio::time::interval(Duration::from_secs(chain.slot_time_sec()));
+
+    loop {
+        interval.tick().await;
+
+        let current_epoch = chain.current_epoch();
+        for epoch in [current_epoch, current_epoch + 1] {
+            if state.proposer_duties(epoch).is_some() {
+                continue;
+            }
+
+            match beacon_client.get_proposer_duties(epoch).await {
+                Ok(duties) => {
+                    debug!(epoch, n_duties = duties.len(), "fetched proposer duties");
+                    state.cache_proposer_duties(epoch, duties);
+                }
+                Err(err) => warn!(%err, epoch, "failed to fetch proposer duties"),
+            }
+        }
+    }
+}
+
+/// Periodically fetches the validator pubkeys managed by the validator client.
+/// If fetching fails the previous pubkeys are kept
+async fn refresh_managed_pubkeys<S: BuilderApiState>(
+    state: PbsState<S>,
+    keymanager_client: KeymanagerClient,
+    refresh: Duration,
+) {
+    let mut interval = tokio::time::interval(refresh);
+
+    loop {
+        interval.tick().await;
+
+        match keymanager_client.get_pubkeys().await {
+            Ok(pubkeys) => {
+                debug!(n_pubkeys = pubkeys.len(), "fetched managed validator pubkeys");
+                state.set_managed_pubkeys(pubkeys);
+            }
+            Err(err) => warn!(%err, "failed to fetch managed validator pubkeys"),
+        }
+    }
+}
+
+/// Periodically POSTs a heartbeat to `url`. Failures are logged and don't stop
+/// the following heartbeats
+async fn send_heartbeats(chain: Chain, url: Url, interval: Duration) {
+    let client = reqwest::Client::new();
+    let mut interval = tokio::time::interval(interval);
+
+    loop {
+        interval.tick().await;
+
+        let heartbeat = Heartbeat { ts: utcnow_sec(), chain, slot: chain.current_slot() };
+        let res = client
+            .post(url.clone())
+            .timeout(DEFAULT_REQUEST_TIMEOUT)
+            .json(&heartbeat)
+            .send()
+            .await
+            .and_then(|res| res.error_for_status());
+
+        match res {
+            Ok(_) => debug!(slot = heartbeat.slot, "sent heartbeat"),
+            Err(err) => warn!(%err, %url, "failed to send heartbeat"),
+        }
+    }
+}
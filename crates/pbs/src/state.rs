@@ -1,16 +1,29 @@
 use std::{
-    collections::HashSet,
-    sync::{Arc, Mutex},
+    collections::{HashSet, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, RwLock,
+    },
 };
 
-use alloy::{primitives::B256, rpc::types::beacon::BlsPublicKey};
+use alloy::{
+    primitives::B256,
+    rpc::types::beacon::{relay::ValidatorRegistration, BlsPublicKey},
+};
 use cb_common::{
     config::{PbsConfig, PbsModuleConfig},
-    pbs::{BuilderEvent, GetHeaderResponse, RelayClient},
+    pbs::{BuilderEvent, GetHeaderResponse, ProposerDuty, RelayClient},
 };
 use dashmap::DashMap;
+use tokio::sync::{broadcast, Semaphore};
+use url::Url;
 use uuid::Uuid;
 
+use crate::{
+    constants::{EVENTS_CHANNEL_CAPACITY, MAX_SUBMITTED_SLOTS},
+    logger::RequestLogger,
+};
+
 pub trait BuilderApiState: Clone + Sync + Send + 'static {}
 impl BuilderApiState for () {}
 
@@ -26,15 +39,53 @@ pub struct PbsState<S: BuilderApiState = ()> {
     current_slot_info: Arc<Mutex<(u64, Uuid)>>,
     /// Keeps track of which relays delivered which block for which slot
     bid_cache: Arc<DashMap<u64, Vec<GetHeaderResponse>>>,
+    /// Slots for which a blinded block was submitted, with the insertion order
+    /// to only keep the most recent ones
+    submitted_slots: Arc<Mutex<(HashSet<u64>, VecDeque<u64>)>>,
+    /// Proposer duties fetched from the consensus client, by epoch
+    proposer_duties: Arc<DashMap<u64, Vec<ProposerDuty>>>,
+    /// Gas limit of the latest registration of each validator
+    registered_gas_limits: Arc<DashMap<BlsPublicKey, u64>>,
+    /// Validator pubkeys fetched from the keymanager API, None until the
+    /// first successful fetch
+    managed_pubkeys: Arc<RwLock<Option<HashSet<BlsPublicKey>>>>,
+    /// Relays from the config and the relay list file, if any
+    relays: Arc<RwLock<Vec<RelayClient>>>,
+    /// Host and port of relays to exclude from all requests
+    excluded_relays: Arc<RwLock<HashSet<(String, u16)>>>,
+    /// Limits how many requests are processed concurrently, if set
+    in_flight_limit: Option<Arc<Semaphore>>,
+    /// Total number of requests received, excluding admin endpoints
+    request_count: Arc<AtomicU64>,
+    /// Number of requests that returned an error status code
+    error_count: Arc<AtomicU64>,
+    /// Custom sink for request logs, if set
+    request_logger: Option<Arc<dyn RequestLogger>>,
+    /// Sends builder events to the events API clients, if it's enabled
+    events_sender: Option<broadcast::Sender<BuilderEvent>>,
 }
 
 impl PbsState<()> {
     pub fn new(config: PbsModuleConfig) -> Self {
+        let events_sender =
+            config.events_api_jwt.is_some().then(|| broadcast::channel(EVENTS_CHANNEL_CAPACITY).0);
+
         Self {
+            relays: Arc::new(RwLock::new(config.relays.clone())),
             config,
             data: (),
             current_slot_info: Arc::new(Mutex::new((0, Uuid::new_v4()))),
             bid_cache: Arc::new(DashMap::new()),
+            submitted_slots: Arc::new(Mutex::new((HashSet::new(), VecDeque::new()))),
+            proposer_duties: Arc::new(DashMap::new()),
+            registered_gas_limits: Arc::new(DashMap::new()),
+            managed_pubkeys: Arc::new(RwLock::new(None)),
+            excluded_relays: Arc::new(RwLock::new(HashSet::new())),
+            in_flight_limit: None,
+            request_count: Arc::new(AtomicU64::new(0)),
+            error_count: Arc::new(AtomicU64::new(0)),
+            request_logger: None,
+            events_sender,
         }
     }
 
@@ -44,6 +95,17 @@ impl PbsState<()> {
             config: self.config,
             current_slot_info: self.current_slot_info,
             bid_cache: self.bid_cache,
+            submitted_slots: self.submitted_slots,
+            proposer_duties: self.proposer_duties,
+            registered_gas_limits: self.registered_gas_limits,
+            managed_pubkeys: self.managed_pubkeys,
+            relays: self.relays,
+            excluded_relays: self.excluded_relays,
+            in_flight_limit: self.in_flight_limit,
+            request_count: self.request_count,
+            error_count: self.error_count,
+            request_logger: self.request_logger,
+            events_sender: self.events_sender,
         }
     }
 }
@@ -52,12 +114,35 @@ impl<S> PbsState<S>
 where
     S: BuilderApiState,
 {
+    /// Rejects incoming requests with 503 when `max_in_flight` requests are
+    /// already being processed, so the beacon node can retry later
+    pub fn with_backpressure_limit(mut self, max_in_flight: usize) -> Self {
+        self.in_flight_limit = Some(Arc::new(Semaphore::new(max_in_flight)));
+        self
+    }
+
+    /// Sends a summary of each request and response to `logger`
+    pub fn with_request_logger(mut self, logger: Arc<dyn RequestLogger>) -> Self {
+        self.request_logger = Some(logger);
+        self
+    }
+
     pub fn publish_event(&self, e: BuilderEvent) {
+        if let Some(sender) = self.events_sender.as_ref() {
+            // fails only if no client is connected
+            let _ = sender.send(e.clone());
+        }
         if let Some(publisher) = self.config.event_publiher.as_ref() {
             publisher.publish(e);
         }
     }
 
+    /// Receives the builder events published from now on, None if the events
+    /// API is disabled
+    pub fn subscribe_events(&self) -> Option<broadcast::Receiver<BuilderEvent>> {
+        self.events_sender.as_ref().map(broadcast::Sender::subscribe)
+    }
+
     pub fn get_or_update_slot_uuid(&self, last_slot: u64) -> Uuid {
         let mut guard = self.current_slot_info.lock().expect("poisoned");
         if guard.0 < last_slot {
@@ -78,8 +163,62 @@ where
     pub fn pbs_config(&self) -> &PbsConfig {
         &self.config.pbs_config
     }
-    pub fn relays(&self) -> &[RelayClient] {
-        &self.config.relays
+    /// Relays to send requests to, i.e. excluding those on the exclusion list
+    pub fn relays(&self) -> Vec<RelayClient> {
+        let relays = self.relays.read().expect("poisoned");
+        let excluded = self.excluded_relays.read().expect("poisoned");
+        if excluded.is_empty() {
+            return relays.clone();
+        }
+
+        relays
+            .iter()
+            .filter(|relay| {
+                url_host_port(&relay.config.entry.url)
+                    .map_or(true, |host_port| !excluded.contains(&host_port))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Number of relays, including those on the exclusion list
+    pub fn relay_count(&self) -> usize {
+        self.relays.read().expect("poisoned").len()
+    }
+
+    /// Replaces the relays, e.g. after reloading the relay list file
+    pub fn set_relays(&self, relays: Vec<RelayClient>) {
+        *self.relays.write().expect("poisoned") = relays;
+    }
+
+    /// Replaces the relay exclusion list
+    pub fn set_excluded_relays(&self, urls: &[Url]) {
+        let excluded = urls.iter().filter_map(url_host_port).collect();
+        *self.excluded_relays.write().expect("poisoned") = excluded;
+    }
+
+    pub fn in_flight_limit(&self) -> Option<&Semaphore> {
+        self.in_flight_limit.as_deref()
+    }
+
+    pub fn request_logger(&self) -> Option<&dyn RequestLogger> {
+        self.request_logger.as_deref()
+    }
+
+    pub fn request_count(&self) -> u64 {
+        self.request_count.load(Ordering::Relaxed)
+    }
+
+    pub fn error_count(&self) -> u64 {
+        self.error_count.load(Ordering::Relaxed)
+    }
+
+    /// Counts a served request, and whether it returned an error
+    pub fn record_request(&self, is_error: bool) {
+        self.request_count.fetch_add(1, Ordering::Relaxed);
+        if is_error {
+            self.error_count.fetch_add(1, Ordering::Relaxed);
+        }
     }
 
     pub fn has_monitors(&self) -> bool {
@@ -112,8 +251,78 @@ where
         })
     }
 
+    /// Marks a blinded block as submitted for the slot. Returns false if a
+    /// block was already submitted for this slot
+    pub fn try_mark_slot_submitted(&self, slot: u64) -> bool {
+        let mut guard = self.submitted_slots.lock().expect("poisoned");
+        let (slots, order) = &mut *guard;
+
+        if !slots.insert(slot) {
+            return false;
+        }
+
+        order.push_back(slot);
+        if order.len() > MAX_SUBMITTED_SLOTS {
+            if let Some(oldest) = order.pop_front() {
+                slots.remove(&oldest);
+            }
+        }
+
+        true
+    }
+
+    /// Clears the mark of a slot whose submission failed, so the beacon node
+    /// can retry it
+    pub fn unmark_slot_submitted(&self, slot: u64) {
+        let mut guard = self.submitted_slots.lock().expect("poisoned");
+        let (slots, order) = &mut *guard;
+
+        if slots.remove(&slot) {
+            order.retain(|s| *s != slot);
+        }
+    }
+
+    /// Cached proposer duties for the epoch, if any
+    pub fn proposer_duties(&self, epoch: u64) -> Option<Vec<ProposerDuty>> {
+        self.proposer_duties.get(&epoch).map(|duties| duties.clone())
+    }
+
+    /// Caches the proposer duties for the epoch, dropping those older than the
+    /// previous epoch
+    pub fn cache_proposer_duties(&self, epoch: u64, duties: Vec<ProposerDuty>) {
+        self.proposer_duties.retain(|cached_epoch, _| *cached_epoch + 1 >= epoch);
+        self.proposer_duties.insert(epoch, duties);
+    }
+
+    /// Gas limit of the latest registration of the validator, if any
+    pub fn registered_gas_limit(&self, pubkey: &BlsPublicKey) -> Option<u64> {
+        self.registered_gas_limits.get(pubkey).map(|gas_limit| *gas_limit)
+    }
+
+    /// Stores the gas limits of the validator registrations
+    pub fn record_registrations(&self, registrations: &[ValidatorRegistration]) {
+        for registration in registrations {
+            let message = &registration.message;
+            self.registered_gas_limits.insert(message.pubkey, message.gas_limit);
+        }
+    }
+
+    /// Validator pubkeys from the latest keymanager API fetch, if any
+    pub fn managed_pubkeys(&self) -> Option<HashSet<BlsPublicKey>> {
+        self.managed_pubkeys.read().expect("poisoned").clone()
+    }
+
+    /// Replaces the validator pubkeys managed by the validator client
+    pub fn set_managed_pubkeys(&self, pubkeys: Vec<BlsPublicKey>) {
+        *self.managed_pubkeys.write().expect("poisoned") = Some(pubkeys.into_iter().collect());
+    }
+
     /// Clear bids which are more than ~3 minutes old
     fn clear(&self, last_slot: u64) {
         self.bid_cache.retain(|slot, _| last_slot.saturating_sub(*slot) < 15)
     }
 }
+
+fn url_host_port(url: &Url) -> Option<(String, u16)> {
+    Some((url.host_str()?.to_string(), url.port_or_known_default()?))
+}
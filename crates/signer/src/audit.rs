@@ -0,0 +1,120 @@
+use std::path::Path;
+
+use cb_common::{types::ModuleId, utils::utcnow_ms};
+use eyre::{Result, WrapErr};
+use serde::Serialize;
+use tokio::{
+    fs::{File, OpenOptions},
+    io::AsyncWriteExt,
+    sync::Mutex,
+};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::metrics::AUDIT_LOG_WRITE_ERRORS;
+
+/// A signing event, written as a single JSON line to the audit log
+#[derive(Debug, Serialize)]
+pub struct AuditRecord<'a> {
+    /// Unix timestamp in milliseconds
+    pub timestamp: u64,
+    pub req_id: Uuid,
+    pub module_id: &'a ModuleId,
+    /// Slot from the request metadata, or the current slot if not provided
+    pub slot: u64,
+    /// Hex encoded public key used to sign
+    pub pubkey: String,
+    /// Hex encoded object root that was signed
+    pub msg_hash: String,
+    /// Purpose from the request metadata, if provided by the module
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<&'a str>,
+}
+
+impl<'a> AuditRecord<'a> {
+    pub fn new(
+        req_id: Uuid,
+        module_id: &'a ModuleId,
+        slot: u64,
+        pubkey: impl AsRef<[u8]>,
+        object_root: &[u8; 32],
+        purpose: Option<&'a str>,
+    ) -> Self {
+        Self {
+            timestamp: utcnow_ms(),
+            req_id,
+            module_id,
+            slot,
+            pubkey: alloy::hex::encode_prefixed(pubkey),
+            msg_hash: alloy::hex::encode_prefixed(object_root),
+            purpose,
+        }
+    }
+}
+
+/// Append-only JSONL log of every signature produced by the signer
+pub struct AuditLog {
+    file: Mutex<File>,
+}
+
+impl AuditLog {
+    pub async fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .wrap_err(format!("Unable to open audit log file: {}", path.display()))?;
+
+        Ok(Self { file: Mutex::new(file) })
+    }
+
+    /// Appends the record to the log and syncs it to disk. Failures are
+    /// logged and counted, but don't fail the signing request
+    pub async fn record(&self, record: AuditRecord<'_>) {
+        if let Err(err) = self.write(&record).await {
+            AUDIT_LOG_WRITE_ERRORS.inc();
+            error!(?err, req_id =% record.req_id, "Failed to write signing event to audit log");
+        }
+    }
+
+    async fn write(&self, record: &AuditRecord<'_>) -> Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        let mut file = self.file.lock().await;
+        file.write_all(&line).await?;
+        file.sync_data().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_audit_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let audit_log = AuditLog::open(&path).await.unwrap();
+
+        let module_id = ModuleId("TEST_MODULE".into());
+        let req_id = Uuid::new_v4();
+        audit_log.record(AuditRecord::new(req_id, &module_id, 10, [1; 48], &[2; 32], None)).await;
+        audit_log
+            .record(AuditRecord::new(req_id, &module_id, 11, [1; 48], &[2; 32], Some("preconf")))
+            .await;
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let records: Vec<serde_json::Value> =
+            content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["slot"], 10);
+        assert_eq!(records[0]["module_id"], "TEST_MODULE");
+        assert!(records[0].get("purpose").is_none());
+        assert_eq!(records[1]["slot"], 11);
+        assert_eq!(records[1]["purpose"], "preconf");
+    }
+}
@@ -3,6 +3,7 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use cb_common::commit::constants::REQUIRED_API_VERSION_HEADER;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -18,15 +19,32 @@ pub enum SignerModuleError {
 
     #[error("internal error {0}")]
     Internal(String),
+
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("incompatible module API version: required {required}, got {got}")]
+    IncompatibleApiVersion { required: String, got: String },
 }
 
 impl IntoResponse for SignerModuleError {
     fn into_response(self) -> Response {
+        if let SignerModuleError::IncompatibleApiVersion { required, .. } = &self {
+            return (
+                StatusCode::UPGRADE_REQUIRED,
+                [(REQUIRED_API_VERSION_HEADER, required.clone())],
+                self.to_string(),
+            )
+                .into_response();
+        }
+
         let status = match self {
             SignerModuleError::Unauthorized => StatusCode::UNAUTHORIZED,
             SignerModuleError::UnknownConsensusSigner(_) => StatusCode::NOT_FOUND,
             SignerModuleError::UnknownProxySigner(_) => StatusCode::NOT_FOUND,
             SignerModuleError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            SignerModuleError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            SignerModuleError::IncompatibleApiVersion { .. } => StatusCode::UPGRADE_REQUIRED,
         };
 
         (status, self.to_string()).into_response()
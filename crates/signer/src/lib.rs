@@ -1,3 +1,5 @@
+pub mod audit;
 pub mod error;
 pub mod manager;
+pub mod metrics;
 pub mod service;
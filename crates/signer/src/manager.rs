@@ -69,6 +69,10 @@ impl SigningManager {
         }
     }
 
+    pub fn chain(&self) -> Chain {
+        self.chain
+    }
+
     pub fn add_consensus_signer(&mut self, signer: ConsensusSigner) {
         self.consensus_signers.insert(signer.pubkey(), signer);
     }
@@ -282,8 +286,7 @@ mod tests {
 
     mod test_proxy_bls {
         use cb_common::{
-            constants::COMMIT_BOOST_DOMAIN, signature::compute_domain,
-            signer::schemes::bls::verify_bls_signature,
+            constants::COMMIT_BOOST_DOMAIN, signer::schemes::bls::verify_bls_signature,
         };
 
         use super::*;
@@ -346,7 +349,7 @@ mod tests {
                 .unwrap();
 
             // Verify signature
-            let domain = compute_domain(CHAIN, COMMIT_BOOST_DOMAIN);
+            let domain = CHAIN.compute_domain(COMMIT_BOOST_DOMAIN);
             let signing_root = compute_signing_root(data_root.tree_hash_root().0, domain);
 
             let validation_result = verify_bls_signature(&proxy_pk, &signing_root, &sig);
@@ -360,8 +363,7 @@ mod tests {
 
     mod test_proxy_ecdsa {
         use cb_common::{
-            constants::COMMIT_BOOST_DOMAIN, signature::compute_domain,
-            signer::schemes::ecdsa::verify_ecdsa_signature,
+            constants::COMMIT_BOOST_DOMAIN, signer::schemes::ecdsa::verify_ecdsa_signature,
         };
 
         use super::*;
@@ -424,7 +426,7 @@ mod tests {
                 .unwrap();
 
             // Verify signature
-            let domain = compute_domain(CHAIN, COMMIT_BOOST_DOMAIN);
+            let domain = CHAIN.compute_domain(COMMIT_BOOST_DOMAIN);
             let signing_root = compute_signing_root(data_root.tree_hash_root().0, domain);
 
             let validation_result = verify_ecdsa_signature(&proxy_pk, &signing_root, &sig);
@@ -0,0 +1,17 @@
+//! Metrics for Signer module
+
+use lazy_static::lazy_static;
+use prometheus::{register_int_counter_with_registry, IntCounter, Registry};
+
+lazy_static! {
+    pub static ref SIGNER_METRICS_REGISTRY: Registry =
+        Registry::new_custom(Some("cb_signer".to_string()), None).unwrap();
+
+    /// Number of signing events that could not be written to the audit log
+    pub static ref AUDIT_LOG_WRITE_ERRORS: IntCounter = register_int_counter_with_registry!(
+        "audit_log_write_errors_total",
+        "Number of signing events that failed to be written to the audit log",
+        SIGNER_METRICS_REGISTRY
+    )
+    .unwrap();
+}
@@ -1,4 +1,4 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::sync::Arc;
 
 use axum::{
     extract::{Request, State},
@@ -12,22 +12,34 @@ use axum_extra::TypedHeader;
 use bimap::BiHashMap;
 use cb_common::{
     commit::{
-        constants::{GENERATE_PROXY_KEY_PATH, GET_PUBKEYS_PATH, REQUEST_SIGNATURE_PATH},
+        constants::{
+            GENERATE_PROXY_KEY_PATH, GET_PUBKEYS_PATH, MODULE_API_VERSION_HEADER,
+            REQUEST_SIGNATURE_PATH, VERIFY_SIGNATURE_PATH,
+        },
         request::{
             EncryptionScheme, GenerateProxyRequest, GetPubkeysResponse, SignConsensusRequest,
-            SignProxyRequest, SignRequest,
+            SignProxyRequest, SignRequest, SignRequestWithMetadata, VerifySignatureRequest,
+            VerifySignatureResponse,
         },
     },
     config::StartSignerConfig,
+    signature::verify_commit_boost_root,
     types::{Jwt, ModuleId},
+    utils::{bind_address, is_compatible_version},
 };
+use cb_metrics::provider::MetricsProvider;
 use eyre::{Result, WrapErr};
 use headers::{authorization::Bearer, Authorization};
 use tokio::{net::TcpListener, sync::RwLock};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use crate::{error::SignerModuleError, manager::SigningManager};
+use crate::{
+    audit::{AuditLog, AuditRecord},
+    error::SignerModuleError,
+    manager::SigningManager,
+    metrics::SIGNER_METRICS_REGISTRY,
+};
 
 /// Implements the Signer API and provides a service for signing requests
 pub struct SigningService;
@@ -39,6 +51,10 @@ struct SigningState {
     /// Map of JWTs to module ids. This also acts as registry of all modules
     /// running
     jwts: Arc<BiHashMap<ModuleId, Jwt>>,
+    /// Module API version that modules need to be compatible with
+    module_api_version: Arc<String>,
+    /// Log of all signing events, if enabled
+    audit_log: Option<Arc<AuditLog>>,
 }
 
 impl SigningService {
@@ -60,16 +76,24 @@ impl SigningService {
             manager.add_consensus_signer(signer);
         }
 
-        let state = SigningState { manager: RwLock::new(manager).into(), jwts: config.jwts.into() };
+        let audit_log = match &config.audit_log_path {
+            Some(path) => {
+                info!(path =% path.display(), "Writing signing events to audit log");
+                Some(Arc::new(AuditLog::open(path).await?))
+            }
+            None => None,
+        };
+
+        let state = SigningState {
+            manager: RwLock::new(manager).into(),
+            jwts: config.jwts.into(),
+            module_api_version: config.module_api_version.into(),
+            audit_log,
+        };
 
-        let app = axum::Router::new()
-            .route(REQUEST_SIGNATURE_PATH, post(handle_request_signature))
-            .route(GET_PUBKEYS_PATH, get(handle_get_pubkeys))
-            .route(GENERATE_PROXY_KEY_PATH, post(handle_generate_proxy))
-            .with_state(state.clone())
-            .route_layer(middleware::from_fn_with_state(state.clone(), jwt_auth));
+        let app = signer_router(state);
 
-        let address = SocketAddr::from(([0, 0, 0, 0], config.server_port));
+        let address = bind_address(config.enable_ipv6, config.server_port);
         let listener = TcpListener::bind(address).await.wrap_err("failed tcp binding")?;
 
         if let Err(err) = axum::serve(listener, app).await {
@@ -77,6 +101,24 @@ impl SigningService {
         }
         Ok(())
     }
+
+    pub fn init_metrics() -> Result<()> {
+        MetricsProvider::load_and_run(SIGNER_METRICS_REGISTRY.clone())
+    }
+}
+
+/// Signer API routes, authenticated with the module JWTs
+fn signer_router(state: SigningState) -> axum::Router {
+    axum::Router::new()
+        .route(REQUEST_SIGNATURE_PATH, post(handle_request_signature))
+        .route(GET_PUBKEYS_PATH, get(handle_get_pubkeys))
+        .route(GENERATE_PROXY_KEY_PATH, post(handle_generate_proxy))
+        .route(VERIFY_SIGNATURE_PATH, post(handle_verify_signature))
+        .with_state(state.clone())
+        // the last layer runs first, so modules are authenticated before their
+        // API version is checked
+        .route_layer(middleware::from_fn_with_state(state.clone(), check_api_version))
+        .route_layer(middleware::from_fn_with_state(state, jwt_auth))
 }
 
 /// Authentication middleware layer
@@ -98,6 +140,33 @@ async fn jwt_auth(
     Ok(next.run(req).await)
 }
 
+/// Rejects requests from modules advertising an incompatible module API
+/// version. Modules not sending a version are allowed through
+async fn check_api_version(
+    State(state): State<SigningState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, SignerModuleError> {
+    if let Some(version) = req.headers().get(MODULE_API_VERSION_HEADER) {
+        let version = String::from_utf8_lossy(version.as_bytes()).into_owned();
+
+        if !is_compatible_version(&state.module_api_version, &version) {
+            warn!(
+                required =% state.module_api_version,
+                got =% version,
+                "Rejected request from module with incompatible API version"
+            );
+
+            return Err(SignerModuleError::IncompatibleApiVersion {
+                required: state.module_api_version.to_string(),
+                got: version,
+            });
+        }
+    }
+
+    Ok(next.run(req).await)
+}
+
 /// Implements get_pubkeys from the Signer API
 async fn handle_get_pubkeys(
     Extension(module_id): Extension<ModuleId>,
@@ -121,12 +190,39 @@ async fn handle_get_pubkeys(
 async fn handle_request_signature(
     Extension(module_id): Extension<ModuleId>,
     State(state): State<SigningState>,
-    Json(request): Json<SignRequest>,
+    Json(SignRequestWithMetadata { request, metadata }): Json<SignRequestWithMetadata>,
 ) -> Result<impl IntoResponse, SignerModuleError> {
     let req_id = Uuid::new_v4();
 
     debug!(event = "request_signature", ?module_id, ?req_id, "New request");
 
+    if let Some(metadata) = &metadata {
+        if metadata.module_id != module_id {
+            warn!(
+                ?module_id,
+                ?req_id,
+                metadata_module_id =? metadata.module_id,
+                "Module id in request metadata does not match authenticated module"
+            );
+        }
+
+        info!(
+            event = "request_signature",
+            ?module_id,
+            ?req_id,
+            client_req_id =% metadata.request_id,
+            purpose = %metadata.purpose,
+            ?request,
+            "Signature request metadata"
+        );
+    }
+
+    let (pubkey, object_root) = match &request {
+        SignRequest::Consensus(req) => (req.pubkey.to_vec(), req.object_root),
+        SignRequest::ProxyBls(req) => (req.pubkey.to_vec(), req.object_root),
+        SignRequest::ProxyEcdsa(req) => (req.pubkey.to_vec(), req.object_root),
+    };
+
     let signing_manager = state.manager.read().await;
 
     let signature_response = match request {
@@ -156,9 +252,42 @@ async fn handle_request_signature(
         }
     }?;
 
+    if let Some(audit_log) = &state.audit_log {
+        let purpose = metadata.as_ref().map(|metadata| metadata.purpose.as_str());
+        let slot = metadata
+            .as_ref()
+            .and_then(|metadata| metadata.slot)
+            .unwrap_or_else(|| signing_manager.chain().current_slot());
+        audit_log
+            .record(AuditRecord::new(req_id, &module_id, slot, pubkey, &object_root, purpose))
+            .await;
+    }
+
     Ok(signature_response)
 }
 
+/// Implements verify_signature from the Signer API
+async fn handle_verify_signature(
+    Extension(module_id): Extension<ModuleId>,
+    Json(request): Json<VerifySignatureRequest>,
+) -> Result<impl IntoResponse, SignerModuleError> {
+    let req_id = Uuid::new_v4();
+
+    debug!(event = "verify_signature", ?module_id, ?req_id, "New request");
+
+    let object_root: [u8; 32] = request.msg.as_ref().try_into().map_err(|_| {
+        SignerModuleError::InvalidRequest(format!(
+            "msg must be a 32 bytes object root, got {} bytes",
+            request.msg.len()
+        ))
+    })?;
+    let valid =
+        verify_commit_boost_root(request.chain, &request.pubkey, object_root, &request.signature)
+            .is_ok();
+
+    Ok((StatusCode::OK, Json(VerifySignatureResponse { valid })).into_response())
+}
+
 async fn handle_generate_proxy(
     Extension(module_id): Extension<ModuleId>,
     State(state): State<SigningState>,
@@ -185,3 +314,93 @@ async fn handle_generate_proxy(
 
     Ok(response)
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Bytes;
+    use cb_common::{
+        commit::{
+            client::SignerClient,
+            constants::{MODULE_API_VERSION, REQUIRED_API_VERSION_HEADER},
+            error::SignerClientError,
+        },
+        signature::sign_commit_boost_root,
+        signer::schemes::bls::random_secret,
+        types::Chain,
+        utils::blst_pubkey_to_alloy,
+    };
+
+    use super::*;
+
+    const JWT: &str = "test-jwt";
+
+    /// Serves the signer API on a free port, with a single module using `JWT`
+    async fn start_signer(chain: Chain, module_api_version: &str) -> reqwest::Url {
+        let mut jwts = BiHashMap::new();
+        jwts.insert(ModuleId("test".into()), Jwt(JWT.into()));
+        let state = SigningState {
+            manager: RwLock::new(SigningManager::new(chain)).into(),
+            jwts: jwts.into(),
+            module_api_version: module_api_version.to_string().into(),
+            audit_log: None,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+        tokio::spawn(async move { axum::serve(listener, signer_router(state)).await });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_auth_before_api_version() {
+        let url = start_signer(Chain::Holesky, "1.0.0").await;
+        let pubkeys_url = url.join(GET_PUBKEYS_PATH).unwrap();
+        let client = reqwest::Client::new();
+
+        let res = client
+            .get(pubkeys_url.clone())
+            .bearer_auth("wrong-jwt")
+            .header(MODULE_API_VERSION_HEADER, "0.1.0")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert!(res.headers().get(REQUIRED_API_VERSION_HEADER).is_none());
+
+        let res = client
+            .get(pubkeys_url)
+            .bearer_auth(JWT)
+            .header(MODULE_API_VERSION_HEADER, "0.1.0")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UPGRADE_REQUIRED);
+        assert_eq!(res.headers()[REQUIRED_API_VERSION_HEADER], "1.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_verify_signature() {
+        let chain = Chain::Holesky;
+        let url = start_signer(chain, MODULE_API_VERSION).await;
+
+        let secret_key = random_secret();
+        let pubkey = blst_pubkey_to_alloy(&secret_key.sk_to_pk()).into();
+        let object_root = [1; 32];
+        let signature = sign_commit_boost_root(chain, &secret_key, object_root);
+        let msg = Bytes::copy_from_slice(&object_root);
+        let client = SignerClient::new(url, JWT).unwrap();
+
+        assert!(client.verify_signature(pubkey, msg.clone(), signature, chain).await.unwrap());
+        // signed with the domain of another chain
+        assert!(!client
+            .verify_signature(pubkey, msg.clone(), signature, Chain::Mainnet)
+            .await
+            .unwrap());
+        let other_msg = Bytes::copy_from_slice(&[2; 32]);
+        assert!(!client.verify_signature(pubkey, other_msg, signature, chain).await.unwrap());
+
+        let short_msg = Bytes::copy_from_slice(&[1; 31]);
+        let err = client.verify_signature(pubkey, short_msg, signature, chain).await.unwrap_err();
+        assert!(matches!(err, SignerClientError::FailedRequest { status: 400, .. }));
+    }
+}
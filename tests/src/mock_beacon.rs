@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use alloy::rpc::types::beacon::BlsPublicKey;
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use cb_common::constants::SLOTS_PER_EPOCH;
+use serde_json::json;
+
+pub struct MockBeaconState {
+    /// Validator returned as proposer for every slot
+    pub proposer: BlsPublicKey,
+}
+
+impl MockBeaconState {
+    pub fn new(proposer: BlsPublicKey) -> Self {
+        Self { proposer }
+    }
+}
+
+pub fn mock_beacon_app_router(state: Arc<MockBeaconState>) -> Router {
+    Router::new()
+        .route("/eth/v1/validator/duties/proposer/:epoch", get(handle_get_proposer_duties))
+        .route("/eth/v1/config/spec", get(handle_get_spec))
+        .route("/eth/v1/beacon/states/head/validators", post(handle_post_validators))
+        .with_state(state)
+}
+
+async fn handle_get_proposer_duties(
+    State(state): State<Arc<MockBeaconState>>,
+    Path(epoch): Path<u64>,
+) -> impl IntoResponse {
+    let duties: Vec<_> = (epoch * SLOTS_PER_EPOCH..(epoch + 1) * SLOTS_PER_EPOCH)
+        .map(|slot| {
+            json!({
+                "pubkey": state.proposer,
+                "validator_index": "1",
+                "slot": slot.to_string(),
+            })
+        })
+        .collect();
+
+    Json(json!({
+        "dependent_root": "0x0000000000000000000000000000000000000000000000000000000000000000",
+        "execution_optimistic": false,
+        "data": duties,
+    }))
+}
+
+async fn handle_get_spec() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/json")], include_str!("../data/holesky_spec_data.json"))
+}
+
+/// No validator is known, so every registration is for a pending validator
+async fn handle_post_validators() -> impl IntoResponse {
+    Json(json!({ "execution_optimistic": false, "finalized": false, "data": [] }))
+}
@@ -1,8 +1,9 @@
 use std::{
+    collections::VecDeque,
     net::SocketAddr,
     sync::{
         atomic::{AtomicU64, Ordering},
-        Arc,
+        Arc, Mutex,
     },
 };
 
@@ -16,8 +17,9 @@ use axum::{
 };
 use cb_common::{
     pbs::{
-        GetHeaderParams, GetHeaderResponse, SubmitBlindedBlockResponse, BUILDER_API_PATH,
-        GET_HEADER_PATH, GET_STATUS_PATH, REGISTER_VALIDATOR_PATH, SUBMIT_BLOCK_PATH,
+        GetHeaderParams, GetHeaderResponse, SignedBlindedBeaconBlock, SubmitBlindedBlockResponse,
+        BUILDER_API_PATH, GET_HEADER_PATH, GET_STATUS_PATH, REGISTER_VALIDATOR_PATH,
+        SUBMIT_BLOCK_PATH,
     },
     signature::sign_builder_root,
     signer::BlsSecretKey,
@@ -45,6 +47,13 @@ pub struct MockRelayState {
     received_get_status: Arc<AtomicU64>,
     received_register_validator: Arc<AtomicU64>,
     received_submit_block: Arc<AtomicU64>,
+    /// Number of get_status requests to fail with 503 before succeeding
+    get_status_failures: Arc<AtomicU64>,
+    /// Number of submit_block requests to fail with 503 before succeeding
+    submit_block_failures: Arc<AtomicU64>,
+    /// Bids to return from get_header in order, with no bid once exhausted.
+    /// If not set, a default bid is returned for every request
+    bids: Mutex<Option<VecDeque<GetHeaderResponse>>>,
 }
 
 impl MockRelayState {
@@ -71,6 +80,37 @@ impl MockRelayState {
             received_get_status: Default::default(),
             received_register_validator: Default::default(),
             received_submit_block: Default::default(),
+            get_status_failures: Default::default(),
+            submit_block_failures: Default::default(),
+            bids: Default::default(),
+        }
+    }
+
+    pub fn with_get_status_failures(self, failures: u64) -> Self {
+        self.get_status_failures.store(failures, Ordering::Relaxed);
+        self
+    }
+
+    pub fn with_submit_block_failures(self, failures: u64) -> Self {
+        self.submit_block_failures.store(failures, Ordering::Relaxed);
+        self
+    }
+
+    /// Returns the given bids in order, signed by this relay
+    pub fn with_bids(self, bids: impl IntoIterator<Item = GetHeaderResponse>) -> Self {
+        *self.bids.lock().expect("poisoned") = Some(bids.into_iter().collect());
+        self
+    }
+
+    fn next_bid(&self) -> Option<GetHeaderResponse> {
+        match self.bids.lock().expect("poisoned").as_mut() {
+            Some(bids) => bids.pop_front(),
+            None => {
+                let mut response = GetHeaderResponse::default();
+                response.data.message.header.block_hash.0[0] = 1;
+                response.data.message.value = U256::from(10);
+                Some(response)
+            }
         }
     }
 }
@@ -92,10 +132,10 @@ async fn handle_get_header(
 ) -> Response {
     state.received_get_header.fetch_add(1, Ordering::Relaxed);
 
-    let mut response = GetHeaderResponse::default();
+    let Some(mut response) = state.next_bid() else {
+        return StatusCode::NO_CONTENT.into_response();
+    };
     response.data.message.header.parent_hash = parent_hash;
-    response.data.message.header.block_hash.0[0] = 1;
-    response.data.message.value = U256::from(10);
     response.data.message.pubkey = blst_pubkey_to_alloy(&state.signer.sk_to_pk());
     let object_root = response.data.message.tree_hash_root().0;
     response.data.signature = sign_builder_root(state.chain, &state.signer, object_root);
@@ -105,6 +145,13 @@ async fn handle_get_header(
 
 async fn handle_get_status(State(state): State<Arc<MockRelayState>>) -> impl IntoResponse {
     state.received_get_status.fetch_add(1, Ordering::Relaxed);
+
+    let failures = state.get_status_failures.load(Ordering::Relaxed);
+    if failures > 0 {
+        state.get_status_failures.store(failures - 1, Ordering::Relaxed);
+        return StatusCode::SERVICE_UNAVAILABLE;
+    }
+
     StatusCode::OK
 }
 
@@ -117,8 +164,20 @@ async fn handle_register_validator(
     StatusCode::OK
 }
 
-async fn handle_submit_block(State(state): State<Arc<MockRelayState>>) -> impl IntoResponse {
+async fn handle_submit_block(
+    State(state): State<Arc<MockRelayState>>,
+    Json(signed_blinded_block): Json<SignedBlindedBeaconBlock>,
+) -> impl IntoResponse {
     state.received_submit_block.fetch_add(1, Ordering::Relaxed);
-    let response = SubmitBlindedBlockResponse::default();
+
+    let failures = state.submit_block_failures.load(Ordering::Relaxed);
+    if failures > 0 {
+        state.submit_block_failures.store(failures - 1, Ordering::Relaxed);
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+
+    let mut response = SubmitBlindedBlockResponse::default();
+    response.data.execution_payload.timestamp =
+        state.chain.slot_to_timestamp(signed_blinded_block.message.slot);
     (StatusCode::OK, Json(response)).into_response()
 }
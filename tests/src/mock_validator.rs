@@ -2,8 +2,10 @@ use alloy::{
     primitives::B256,
     rpc::types::beacon::{relay::ValidatorRegistration, BlsPublicKey},
 };
-use cb_common::pbs::{GetHeaderResponse, RelayClient, SignedBlindedBeaconBlock};
-use reqwest::Error;
+use cb_common::pbs::{
+    GetHeaderResponse, RelayClient, SignedBlindedBeaconBlock, HEALTH_PATH, STATS_PATH,
+};
+use reqwest::{Error, Response};
 
 use crate::utils::generate_mock_relay;
 
@@ -16,20 +18,36 @@ impl MockValidator {
         Ok(Self { comm_boost: generate_mock_relay(port, BlsPublicKey::default())? })
     }
 
-    pub async fn do_get_header(&self) -> Result<(), Error> {
-        let url = self.comm_boost.get_header_url(0, B256::ZERO, BlsPublicKey::ZERO).unwrap();
+    pub async fn do_get_header(&self, slot: u64) -> Result<(), Error> {
+        let url = self.comm_boost.get_header_url(slot, B256::ZERO, BlsPublicKey::ZERO).unwrap();
         let res = self.comm_boost.client.get(url).send().await?.bytes().await?;
         assert!(serde_json::from_slice::<GetHeaderResponse>(&res).is_ok());
 
         Ok(())
     }
 
-    pub async fn do_get_status(&self) -> Result<(), Error> {
+    pub async fn do_get_header_for_pubkey(
+        &self,
+        slot: u64,
+        pubkey: BlsPublicKey,
+    ) -> Result<Response, Error> {
+        let url = self.comm_boost.get_header_url(slot, B256::ZERO, pubkey).unwrap();
+        self.comm_boost.client.get(url).send().await
+    }
+
+    pub async fn do_get_status(&self) -> Result<Response, Error> {
         let url = self.comm_boost.get_status_url().unwrap();
-        let _res = self.comm_boost.client.get(url).send().await?;
-        // assert!(res.status().is_success());
+        self.comm_boost.client.get(url).send().await
+    }
 
-        Ok(())
+    pub async fn do_health(&self) -> Result<Response, Error> {
+        let url = self.comm_boost.get_url(HEALTH_PATH).unwrap();
+        self.comm_boost.client.get(url).send().await
+    }
+
+    pub async fn do_stats(&self) -> Result<Response, Error> {
+        let url = self.comm_boost.get_url(STATS_PATH).unwrap();
+        self.comm_boost.client.get(url).send().await
     }
 
     pub async fn do_register_validator(&self) -> Result<(), Error> {
@@ -49,6 +67,14 @@ impl MockValidator {
         Ok(())
     }
 
+    pub async fn do_register_validators(
+        &self,
+        registrations: Vec<ValidatorRegistration>,
+    ) -> Result<Response, Error> {
+        let url = self.comm_boost.register_validator_url().unwrap();
+        self.comm_boost.client.post(url).json(&registrations).send().await
+    }
+
     pub async fn do_submit_block(&self) -> Result<(), Error> {
         let url = self.comm_boost.submit_block_url().unwrap();
 
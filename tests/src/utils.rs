@@ -1,16 +1,42 @@
-use std::sync::Once;
+use std::sync::{Arc, Once};
 
 use alloy::rpc::types::beacon::BlsPublicKey;
+use axum::Router;
 use cb_common::{
     config::RelayConfig,
     pbs::{RelayClient, RelayEntry},
+    utils::blst_pubkey_to_alloy,
 };
 use eyre::Result;
+use tokio::net::TcpListener;
+
+use crate::mock_relay::{mock_relay_app_router, MockRelayState};
 
 pub fn get_local_address(port: u16) -> String {
     format!("http://0.0.0.0:{port}")
 }
 
+/// Port picked by the OS, for servers that bind their own listener
+pub fn get_free_port() -> Result<u16> {
+    let listener = std::net::TcpListener::bind("0.0.0.0:0")?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// Serves `app` on a port picked by the OS, returning the port
+pub async fn serve_on_free_port(app: Router) -> Result<u16> {
+    let listener = TcpListener::bind("0.0.0.0:0").await?;
+    let port = listener.local_addr()?.port();
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    Ok(port)
+}
+
+/// Serves a mock relay on a free port, returning its config
+pub async fn start_mock_relay(state: Arc<MockRelayState>) -> Result<RelayConfig> {
+    let pubkey = blst_pubkey_to_alloy(&state.signer.sk_to_pk());
+    let port = serve_on_free_port(mock_relay_app_router(state)).await?;
+    generate_mock_relay_config(port, pubkey)
+}
+
 static SYNC_SETUP: Once = Once::new();
 pub fn setup_test_env() {
     SYNC_SETUP.call_once(|| {
@@ -19,15 +45,22 @@ pub fn setup_test_env() {
 }
 
 pub fn generate_mock_relay(port: u16, pubkey: BlsPublicKey) -> Result<RelayClient> {
+    RelayClient::new(generate_mock_relay_config(port, pubkey)?)
+}
+
+pub fn generate_mock_relay_config(port: u16, pubkey: BlsPublicKey) -> Result<RelayConfig> {
     let entry =
         RelayEntry { id: format!("mock_{port}"), pubkey, url: get_local_address(port).parse()? };
-    let config = RelayConfig {
+    Ok(RelayConfig {
         entry,
         id: None,
         headers: None,
         enable_timing_games: false,
         target_first_request_ms: None,
         frequency_get_header_ms: None,
-    };
-    RelayClient::new(config)
+        max_bid_wei: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        retry_policy: Default::default(),
+    })
 }
@@ -1,4 +1,21 @@
-use cb_common::{config::CommitBoostConfig, types::Chain};
+use std::sync::Arc;
+
+use alloy::{
+    primitives::{B256, U256},
+    rpc::types::beacon::BlsPublicKey,
+};
+use cb_common::{
+    config::{
+        load_relay_list_file, CommitBoostConfig, ConfigError, HealthCheckOnStartup, ModuleKind,
+        CHAIN_ENV,
+    },
+    loader::SignerLoader,
+    types::{load_chain_from_url, Chain, KnownChain},
+};
+use cb_tests::{
+    mock_beacon::{mock_beacon_app_router, MockBeaconState},
+    utils::{get_local_address, serve_on_free_port},
+};
 use eyre::Result;
 
 #[test]
@@ -7,6 +24,350 @@ fn test_load_config() -> Result<()> {
 
     assert_eq!(config.chain, Chain::Holesky);
     assert!(config.relays[0].headers.is_some());
+    assert_eq!(config.relays[0].connect_timeout_ms, Some(1000));
+    assert_eq!(config.relays[0].request_timeout_ms, Some(12000));
+    assert_eq!(config.pbs.pbs_config.client.max_payload_size_bytes, 10 * 1024 * 1024);
+
+    Ok(())
+}
+
+#[test]
+fn test_load_relay_list_file() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("relay-list.toml");
+    std::fs::write(
+        &path,
+        r#"
+        [[relays]]
+        id = "relay-1"
+        url = "http://0xa1cec75a3f0661e99299274182938151e8433c61a19222347ea1313d839229cb4ce4e3e5aa2bdeb71c8fcf1b084963c2@abc.xyz"
+
+        [[relays]]
+        url = "http://0xa1cec75a3f0661e99299274182938151e8433c61a19222347ea1313d839229cb4ce4e3e5aa2bdeb71c8fcf1b084963c2@def.xyz"
+        enable_timing_games = true
+        "#,
+    )?;
+    let relays = load_relay_list_file(&path)?;
+
+    assert_eq!(relays.len(), 2);
+    assert_eq!(relays[0].id.as_deref(), Some("relay-1"));
+    assert!(relays[1].enable_timing_games);
+
+    Ok(())
+}
+
+#[test]
+fn test_write_config() -> Result<()> {
+    let config = CommitBoostConfig::from_file("../config.example.toml")?;
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    config.write_to_file(&path)?;
+    let written = CommitBoostConfig::from_file(path.to_str().unwrap())?;
+
+    assert_eq!(written.chain, config.chain);
+    assert_eq!(written.relays.len(), config.relays.len());
+    assert_eq!(written.relays[0].entry.url, config.relays[0].entry.url);
+    assert_eq!(written.relays[0].max_bid_wei, config.relays[0].max_bid_wei);
+    assert_eq!(written.pbs.pbs_config.min_bid_wei, config.pbs.pbs_config.min_bid_wei);
+
+    Ok(())
+}
+
+#[test]
+fn test_signing_domain_override() -> Result<()> {
+    let mut config = CommitBoostConfig::from_file("../config.example.toml")?;
+    config.pbs_global.signing_domain_override = Some(B256::repeat_byte(1));
+    assert!(config.validate().is_ok());
+
+    config.chain = Chain::Mainnet;
+    assert!(config.validate().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_validator_pubkeys_with_consensus_client() -> Result<()> {
+    let mut config = CommitBoostConfig::from_file("../config.example.toml")?;
+    config.pbs.pbs_config.validator_pubkeys = vec![BlsPublicKey::repeat_byte(1)];
+    assert!(config.validate().is_ok());
+
+    config.pbs.pbs_config.consensus_client_endpoint = Some("http://localhost:5052".parse()?);
+    assert!(config.validate().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_keymanager_api_endpoint() -> Result<()> {
+    let mut config = CommitBoostConfig::from_file("../config.example.toml")?;
+    assert_eq!(config.pbs_global.keymanager_refresh_secs, 60);
+    config.pbs_global.keymanager_api_endpoint = Some("http://localhost:5062".parse()?);
+    assert!(config.validate().is_ok());
+
+    // both filters apply
+    config.pbs.pbs_config.consensus_client_endpoint = Some("http://localhost:5052".parse()?);
+    assert!(config.validate().is_ok());
+
+    config.pbs.pbs_config.validator_pubkeys = vec![BlsPublicKey::repeat_byte(1)];
+    assert!(config.validate().is_err());
+
+    config.pbs.pbs_config.validator_pubkeys = vec![];
+    config.pbs_global.keymanager_refresh_secs = 0;
+    assert!(config.validate().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_beacon_client_timeout() -> Result<()> {
+    let mut config = CommitBoostConfig::from_file("../config.example.toml")?;
+    assert_eq!(config.pbs_global.beacon_client_timeout_ms, 500);
+    config.pbs.pbs_config.consensus_client_endpoint = Some("http://localhost:5052".parse()?);
+    assert!(config.validate().is_ok());
+
+    config.pbs_global.beacon_client_timeout_ms = config.pbs.pbs_config.timeout_get_header_ms;
+    assert!(config.validate().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_relay_timeout_out_of_range() -> Result<()> {
+    let mut config = CommitBoostConfig::from_file("../config.example.toml")?;
+    config.pbs.pbs_config.relay_timeout_ms = 10;
+    let err = config.validate().unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ConfigError>(),
+        Some(&ConfigError::TimeoutOutOfRange { timeout_ms: 10, min_ms: 50, max_ms: 12_000 })
+    );
+
+    config.pbs.pbs_config.relay_timeout_ms = 12_000;
+    assert!(config.validate().is_ok());
+
+    config.chain = Chain::Gnosis;
+    assert!(config.validate().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_relay_duplicate_check() -> Result<()> {
+    let mut config = CommitBoostConfig::from_file("../config.example.toml")?;
+    assert!(config.pbs_global.relay_duplicate_check);
+
+    config.relays.push(config.relays[0].clone());
+    assert!(config.validate().is_err());
+
+    config.pbs_global.relay_duplicate_check = false;
+    assert!(config.validate().is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_require_https() -> Result<()> {
+    let mut config = CommitBoostConfig::from_file("../config.example.toml")?;
+    assert!(!config.pbs.pbs_config.is_https_required(config.chain));
+    assert!(config.pbs.pbs_config.is_https_required(Chain::Mainnet));
+
+    config.pbs.pbs_config.require_https = Some(false);
+    assert!(!config.pbs.pbs_config.is_https_required(Chain::Mainnet));
+
+    Ok(())
+}
+
+#[test]
+fn test_startup_health_check() -> Result<()> {
+    let mut config = CommitBoostConfig::from_file("../config.example.toml")?;
+    let pbs_config = &mut config.pbs.pbs_config;
+    assert_eq!(pbs_config.startup_health_check(Chain::Holesky), HealthCheckOnStartup::Warn);
+    assert_eq!(pbs_config.startup_health_check(Chain::Mainnet), HealthCheckOnStartup::Strict);
+
+    pbs_config.health_check_on_startup = Some(HealthCheckOnStartup::Skip);
+    assert_eq!(pbs_config.startup_health_check(Chain::Mainnet), HealthCheckOnStartup::Skip);
+
+    Ok(())
+}
+
+const BASE_YAML: &str = r#"
+chain: Holesky
+pbs:
+  port: 18550
+  relay_timeout_ms: 1000
+relays:
+  - url: http://0xa1cec75a3f0661e99299274182938151e8433c61a19222347ea1313d839229cb4ce4e3e5aa2bdeb71c8fcf1b084963c2@abc.xyz
+"#;
+
+#[test]
+fn test_from_yaml_pbs_and_relays() -> Result<()> {
+    let yaml = r#"
+chain: Holesky
+strict_mode: true
+relay_set_name: mev-relays
+pbs:
+  port: 18550
+  relay_check: false
+  relay_timeout_ms: 4000
+  timeout_get_header_ms: 800
+  min_bid_eth: 0.5
+  late_in_slot_time_ms: 1500
+relays:
+  - id: example-relay
+    url: http://0xa1cec75a3f0661e99299274182938151e8433c61a19222347ea1313d839229cb4ce4e3e5aa2bdeb71c8fcf1b084963c2@abc.xyz
+    headers:
+      X-MyCustomHeader: MyCustomValue
+    max_bid_eth: 100.0
+    retry_policy:
+      max_retries: 2
+"#;
+    let config = CommitBoostConfig::from_yaml(yaml)?;
+
+    assert_eq!(config.chain, Chain::Holesky);
+    assert!(config.pbs_global.strict_mode);
+    assert_eq!(config.pbs_global.relay_set_name.as_deref(), Some("mev-relays"));
+
+    let pbs = &config.pbs.pbs_config;
+    assert_eq!(pbs.port, 18550);
+    assert!(!pbs.relay_check);
+    assert_eq!(pbs.relay_timeout_ms, 4000);
+    assert_eq!(pbs.timeout_get_header_ms, 800);
+    assert_eq!(pbs.min_bid_wei, U256::from(500_000_000_000_000_000u64));
+    assert_eq!(pbs.late_in_slot_time_ms, 1500);
+
+    let relay = &config.relays[0];
+    assert_eq!(relay.id.as_deref(), Some("example-relay"));
+    assert_eq!(relay.entry.id, "abc.xyz");
+    assert_eq!(relay.headers.as_ref().unwrap()["X-MyCustomHeader"], "MyCustomValue");
+    assert_eq!(relay.retry_policy.max_retries, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_strict_mode_rejects_skip_sigverify() -> Result<()> {
+    let yaml = BASE_YAML.replace("pbs:\n", "strict_mode: true\npbs:\n  skip_sigverify: true\n");
+    let err = CommitBoostConfig::from_yaml(&yaml).unwrap_err();
+    assert!(err.to_string().contains("skip_sigverify can't be enabled in strict mode"));
+
+    let yaml = BASE_YAML.replace("pbs:\n", "strict_mode: true\npbs:\n");
+    assert!(CommitBoostConfig::from_yaml(&yaml)?.pbs_global.strict_mode);
+
+    Ok(())
+}
+
+#[test]
+fn test_from_yaml_custom_chain() -> Result<()> {
+    let yaml = BASE_YAML.replace(
+        "chain: Holesky",
+        "chain:\n  genesis_time_secs: 1\n  slot_time_secs: 2\n  genesis_fork_version: \"0x01000000\"",
+    );
+    let config = CommitBoostConfig::from_yaml(&yaml)?;
+
+    assert_eq!(config.chain.genesis_time_sec(), 1);
+    assert_eq!(config.chain.slot_time_sec(), 2);
+    assert_eq!(config.chain.genesis_fork_version(), [1, 0, 0, 0]);
+
+    Ok(())
+}
+
+#[test]
+fn test_from_yaml_signer() -> Result<()> {
+    let yaml = format!(
+        "{BASE_YAML}
+signer:
+  loader:
+    key_path: ./keys.example.json
+"
+    );
+    let config = CommitBoostConfig::from_yaml(&yaml)?;
+
+    let signer = config.signer.unwrap();
+    assert!(
+        matches!(signer.loader, SignerLoader::File { key_path } if key_path == "./keys.example.json")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_from_yaml_modules() -> Result<()> {
+    let yaml = format!(
+        "{BASE_YAML}
+modules:
+  - id: DA_COMMIT
+    type: commit
+    docker_image: test_da_commit
+    env:
+      SOME_ENV_VAR: some_value
+"
+    );
+    let config = CommitBoostConfig::from_yaml(&yaml)?;
+
+    let modules = config.modules.unwrap();
+    assert_eq!(modules[0].id.as_str(), "DA_COMMIT");
+    assert!(matches!(modules[0].kind, ModuleKind::Commit));
+    assert_eq!(modules[0].env.as_ref().unwrap()["SOME_ENV_VAR"], "some_value");
+
+    Ok(())
+}
+
+#[test]
+fn test_from_yaml_metrics_and_logs() -> Result<()> {
+    let yaml = format!(
+        "{BASE_YAML}
+metrics:
+  prometheus_config: ./docker/prometheus.yml
+  use_grafana: false
+logs:
+  log_level: debug
+  max_log_files: 30
+"
+    );
+    let config = CommitBoostConfig::from_yaml(&yaml)?;
+
+    let metrics = config.metrics.unwrap();
+    assert_eq!(metrics.prometheus_config, "./docker/prometheus.yml");
+    assert!(!metrics.use_grafana);
+    assert!(metrics.use_cadvisor);
+
+    let logs = config.logs.unwrap();
+    assert_eq!(logs.log_level, "debug");
+    assert_eq!(logs.max_log_files, Some(30));
+
+    Ok(())
+}
+
+#[test]
+fn test_from_yaml_chain_from_env() -> Result<()> {
+    let yaml = BASE_YAML.replace("chain: Holesky\n", "");
+    assert!(CommitBoostConfig::from_yaml(&yaml).is_err());
+
+    std::env::set_var(CHAIN_ENV, "sepolia");
+    let config = CommitBoostConfig::from_yaml(&yaml);
+    std::env::remove_var(CHAIN_ENV);
+    assert_eq!(config?.chain, Chain::Sepolia);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_chain_from_url() -> Result<()> {
+    let beacon_state = Arc::new(MockBeaconState::new(BlsPublicKey::repeat_byte(1)));
+    let port = serve_on_free_port(mock_beacon_app_router(beacon_state)).await?;
+
+    let url = format!("{}/eth/v1/config/spec", get_local_address(port));
+    let chain = load_chain_from_url(&url).await?;
+    assert_eq!(chain, Chain::Custom {
+        genesis_time_secs: KnownChain::Holesky.genesis_time_sec(),
+        slot_time_secs: KnownChain::Holesky.slot_time_sec(),
+        genesis_fork_version: KnownChain::Holesky.genesis_fork_version(),
+        evm_chain_id: Some(17000),
+        genesis_validators_root: None,
+        fork_schedule: Some(KnownChain::Holesky.fork_schedule()),
+    });
 
+    let bad_url = format!("{}/eth/v1/config/missing", get_local_address(port));
+    assert!(load_chain_from_url(&bad_url).await.is_err());
     Ok(())
 }
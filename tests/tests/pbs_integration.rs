@@ -1,33 +1,71 @@
-use std::{sync::Arc, time::Duration, u64};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, SystemTime},
+    u64,
+};
 
-use alloy::primitives::U256;
+use alloy::{
+    primitives::{B256, U256},
+    rpc::types::beacon::relay::ValidatorRegistration,
+};
+use axum::{
+    http::HeaderMap,
+    routing::{get, post},
+    Json, Router,
+};
 use cb_common::{
-    config::{PbsConfig, PbsModuleConfig},
-    pbs::RelayClient,
+    config::{PbsConfig, PbsModuleConfig, RelayRetryPolicy},
+    pbs::{
+        BeaconApiClient, ExecutionApiClient, GetHeaderParams, GetHeaderResponse, HealthResponse,
+        Heartbeat, RelayClient, StatsResponse, EVENTS_PATH,
+    },
     signer::{schemes::bls::random_secret, BlsPublicKey},
-    types::Chain,
-    utils::blst_pubkey_to_alloy,
+    types::{Chain, Jwt},
+};
+use cb_pbs::{
+    get_header_stream, DefaultBuilderApi, PbsService, PbsState, RequestLogger, RequestSummary,
+    ResponseSummary,
 };
-use cb_pbs::{DefaultBuilderApi, PbsService, PbsState};
 use cb_tests::{
-    mock_relay::{start_mock_relay_service, MockRelayState},
+    mock_beacon::{mock_beacon_app_router, MockBeaconState},
+    mock_relay::MockRelayState,
     mock_validator::MockValidator,
-    utils::{generate_mock_relay, setup_test_env},
+    utils::{
+        get_free_port, get_local_address, serve_on_free_port, setup_test_env, start_mock_relay,
+    },
 };
 use eyre::Result;
+use futures::StreamExt;
+use reqwest::StatusCode;
 use tracing::info;
 
 fn get_pbs_static_config(port: u16) -> PbsConfig {
     PbsConfig {
         port,
         relay_check: true,
+        relay_timeout_ms: u64::MAX,
         timeout_get_header_ms: u64::MAX,
         timeout_get_payload_ms: u64::MAX,
         timeout_register_validator_ms: u64::MAX,
         skip_sigverify: false,
         min_bid_wei: U256::ZERO,
+        gas_limit_tolerance_bps: None,
         late_in_slot_time_ms: u64::MAX,
+        max_slot_lookahead: 2,
         relay_monitors: vec![],
+        consensus_client_endpoint: None,
+        validator_pubkeys: vec![],
+        execution_client_endpoint: None,
+        require_https: None,
+        relay_exclusion_list_url: None,
+        relay_exclusion_list_refresh_secs: 3600,
+        simulate_mode: false,
+        simulate_bid_value_gwei: 1_000_000,
+        health_check_on_startup: None,
+        client: Default::default(),
     }
 }
 
@@ -38,32 +76,56 @@ fn to_pbs_config(chain: Chain, pbs_config: PbsConfig, relays: Vec<RelayClient>)
         signer_client: None,
         event_publiher: None,
         relays,
+        enable_ipv6: false,
+        pbs_global: Default::default(),
+        beacon_client: None,
+        execution_client: None,
+        feature_flags: Default::default(),
+        uptime_start: SystemTime::now(),
+        keymanager_client: None,
+        events_api_jwt: None,
     }
 }
 
-#[tokio::test]
-async fn test_get_header() -> Result<()> {
-    setup_test_env();
-    let signer = random_secret();
-    let pubkey: BlsPublicKey = blst_pubkey_to_alloy(&signer.sk_to_pk()).into();
+/// Mock relay state with a random signer
+fn new_mock_state(chain: Chain) -> MockRelayState {
+    MockRelayState::new(chain, random_secret())
+}
 
-    let chain = Chain::Holesky;
-    let port = 3000;
+/// Serves `count` mock relays sharing `mock_state` and returns a PBS config
+/// forwarding to them, all on free ports
+async fn setup_pbs(mock_state: &Arc<MockRelayState>, count: usize) -> Result<PbsModuleConfig> {
+    let mut relays = Vec::with_capacity(count);
+    for _ in 0..count {
+        relays.push(RelayClient::new(start_mock_relay(mock_state.clone()).await?)?);
+    }
 
-    let mock_state = Arc::new(MockRelayState::new(chain, signer));
-    let mock_relay = generate_mock_relay(port + 1, *pubkey)?;
-    tokio::spawn(start_mock_relay_service(mock_state.clone(), port + 1));
+    let port = get_free_port()?;
+    Ok(to_pbs_config(mock_state.chain, get_pbs_static_config(port), relays))
+}
 
-    let config = to_pbs_config(chain, get_pbs_static_config(port), vec![mock_relay]);
-    let state = PbsState::new(config);
+/// Runs the PBS service and returns a validator sending requests to it
+async fn start_pbs(state: PbsState) -> Result<MockValidator> {
+    let port = state.config.pbs_config.port;
     tokio::spawn(PbsService::run::<(), DefaultBuilderApi>(state));
 
     // leave some time to start servers
     tokio::time::sleep(Duration::from_millis(100)).await;
 
-    let mock_validator = MockValidator::new(port)?;
+    MockValidator::new(port)
+}
+
+#[tokio::test]
+async fn test_get_header() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
     info!("Sending get header");
-    let res = mock_validator.do_get_header().await;
+    let res = mock_validator.do_get_header(chain.current_slot()).await;
 
     assert!(res.is_ok());
     assert_eq!(mock_state.received_get_header(), 1);
@@ -71,28 +133,43 @@ async fn test_get_header() -> Result<()> {
 }
 
 #[tokio::test]
-async fn test_get_status() -> Result<()> {
+async fn test_get_header_queued_bids() -> Result<()> {
     setup_test_env();
-    let signer = random_secret();
-    let pubkey: BlsPublicKey = blst_pubkey_to_alloy(&signer.sk_to_pk()).into();
-
     let chain = Chain::Holesky;
-    let port = 3100;
 
-    let relays =
-        vec![generate_mock_relay(port + 1, *pubkey)?, generate_mock_relay(port + 2, *pubkey)?];
-    let mock_state = Arc::new(MockRelayState::new(chain, signer));
-    tokio::spawn(start_mock_relay_service(mock_state.clone(), port + 1));
-    tokio::spawn(start_mock_relay_service(mock_state.clone(), port + 2));
+    let bids = [1u8, 2].map(|i| {
+        let mut bid = GetHeaderResponse::default();
+        bid.data.message.header.block_hash = B256::repeat_byte(i);
+        bid.data.message.value = U256::from(i);
+        bid
+    });
+    let mock_state = Arc::new(new_mock_state(chain).with_bids(bids));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
 
-    let config = to_pbs_config(chain, get_pbs_static_config(port), relays);
-    let state = PbsState::new(config);
-    tokio::spawn(PbsService::run::<(), DefaultBuilderApi>(state));
+    let slot = chain.current_slot();
+    for i in [1u8, 2] {
+        let res = mock_validator.do_get_header_for_pubkey(slot, BlsPublicKey::ZERO).await?;
+        assert_eq!(res.status(), StatusCode::OK);
+        let bid: GetHeaderResponse = res.json().await?;
+        assert_eq!(bid.block_hash(), B256::repeat_byte(i));
+    }
 
-    // leave some time to start servers
-    tokio::time::sleep(Duration::from_millis(100)).await;
+    let res = mock_validator.do_get_header_for_pubkey(slot, BlsPublicKey::ZERO).await?;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    assert_eq!(mock_state.received_get_header(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_status() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 2).await?;
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
 
-    let mock_validator = MockValidator::new(port)?;
     info!("Sending get status");
     let res = mock_validator.do_get_status().await;
 
@@ -102,26 +179,36 @@ async fn test_get_status() -> Result<()> {
 }
 
 #[tokio::test]
-async fn test_register_validators() -> Result<()> {
+async fn test_get_status_retry() -> Result<()> {
     setup_test_env();
-    let signer = random_secret();
-    let pubkey: BlsPublicKey = blst_pubkey_to_alloy(&signer.sk_to_pk()).into();
-
     let chain = Chain::Holesky;
-    let port = 3300;
 
-    let relays = vec![generate_mock_relay(port + 1, *pubkey)?];
-    let mock_state = Arc::new(MockRelayState::new(chain, signer));
-    tokio::spawn(start_mock_relay_service(mock_state.clone(), port + 1));
+    let mock_state = Arc::new(new_mock_state(chain).with_get_status_failures(2));
+    let mut relay_config = start_mock_relay(mock_state.clone()).await?;
+    relay_config.retry_policy =
+        RelayRetryPolicy { max_retries: 2, initial_backoff_ms: 10, max_backoff_ms: 100 };
+    let relays = vec![RelayClient::new(relay_config)?];
 
-    let config = to_pbs_config(chain, get_pbs_static_config(port), relays);
-    let state = PbsState::new(config);
-    tokio::spawn(PbsService::run::<(), DefaultBuilderApi>(state));
+    let config = to_pbs_config(chain, get_pbs_static_config(get_free_port()?), relays);
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
 
-    // leave some time to start servers
-    tokio::time::sleep(Duration::from_millis(100)).await;
+    info!("Sending get status");
+    let res = mock_validator.do_get_status().await?;
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(mock_state.received_get_status(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_register_validators() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
 
-    let mock_validator = MockValidator::new(port)?;
     info!("Sending register validator");
     let res = mock_validator.do_register_validator().await;
 
@@ -131,26 +218,61 @@ async fn test_register_validators() -> Result<()> {
 }
 
 #[tokio::test]
-async fn test_submit_block() -> Result<()> {
+async fn test_register_validators_none_active() -> Result<()> {
     setup_test_env();
-    let signer = random_secret();
-    let pubkey: BlsPublicKey = blst_pubkey_to_alloy(&signer.sk_to_pk()).into();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let mut config = setup_pbs(&mock_state, 1).await?;
+
+    // the mock beacon node knows no validators, so all of them are pending
+    let beacon_state = Arc::new(MockBeaconState::new(BlsPublicKey::repeat_byte(1)));
+    let beacon_port = serve_on_free_port(mock_beacon_app_router(beacon_state)).await?;
+    let beacon_url = get_local_address(beacon_port).parse()?;
+    config.beacon_client = Some(BeaconApiClient::new(beacon_url, Duration::from_millis(2000))?);
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending register validator with only pending validators");
+    let registrations: Vec<ValidatorRegistration> =
+        serde_json::from_str(include_str!("../data/registration_holesky.json"))?;
+    let res = mock_validator.do_register_validators(registrations).await?;
 
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(mock_state.received_register_validator(), 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_register_validators_none_managed() -> Result<()> {
+    setup_test_env();
     let chain = Chain::Holesky;
-    let port = 3400;
 
-    let relays = vec![generate_mock_relay(port + 1, *pubkey)?];
-    let mock_state = Arc::new(MockRelayState::new(chain, signer));
-    tokio::spawn(start_mock_relay_service(mock_state.clone(), port + 1));
+    let mock_state = Arc::new(new_mock_state(chain));
+    let mut config = setup_pbs(&mock_state, 1).await?;
+    let mut pbs_config = (*config.pbs_config).clone();
+    pbs_config.validator_pubkeys = vec![BlsPublicKey::repeat_byte(1)];
+    config.pbs_config = Arc::new(pbs_config);
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
 
-    let config = to_pbs_config(chain, get_pbs_static_config(port), relays);
-    let state = PbsState::new(config);
-    tokio::spawn(PbsService::run::<(), DefaultBuilderApi>(state));
+    info!("Sending register validator with only unmanaged validators");
+    let registrations: Vec<ValidatorRegistration> =
+        serde_json::from_str(include_str!("../data/registration_holesky.json"))?;
+    let res = mock_validator.do_register_validators(registrations).await?;
 
-    // leave some time to start servers
-    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(mock_state.received_register_validator(), 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_submit_block() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
 
-    let mock_validator = MockValidator::new(port)?;
     info!("Sending submit block");
     let res = mock_validator.do_submit_block().await;
 
@@ -158,3 +280,478 @@ async fn test_submit_block() -> Result<()> {
     assert_eq!(mock_state.received_submit_block(), 1);
     Ok(())
 }
+
+#[tokio::test]
+async fn test_backpressure_limit() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let mock_validator = start_pbs(PbsState::new(config).with_backpressure_limit(0)).await?;
+
+    info!("Sending get status");
+    let res = mock_validator.do_get_status().await?;
+
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(mock_state.received_get_status(), 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_enable_ipv6() -> Result<()> {
+    setup_test_env();
+    if std::net::TcpListener::bind("[::1]:0").is_err() {
+        info!("IPv6 is not available, skipping");
+        return Ok(());
+    }
+
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let mut config = setup_pbs(&mock_state, 1).await?;
+    config.enable_ipv6 = true;
+    let port = config.pbs_config.port;
+    start_pbs(PbsState::new(config)).await?;
+
+    for host in ["127.0.0.1", "[::1]"] {
+        info!(host, "Sending get status");
+        let res = reqwest::get(format!("http://{host}:{port}/eth/v1/builder/status")).await?;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    assert_eq!(mock_state.received_get_status(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_submit_block_twice() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending submit block");
+    assert!(mock_validator.do_submit_block().await.is_ok());
+
+    info!("Sending submit block again");
+    let err = mock_validator.do_submit_block().await.unwrap_err();
+
+    assert_eq!(err.status(), Some(StatusCode::CONFLICT));
+    assert_eq!(mock_state.received_submit_block(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_submit_block_retry_after_failure() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain).with_submit_block_failures(1));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending submit block, failing at the relay");
+    assert!(mock_validator.do_submit_block().await.is_err());
+
+    info!("Retrying submit block");
+    assert!(mock_validator.do_submit_block().await.is_ok());
+    assert_eq!(mock_state.received_submit_block(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_header_stream() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 2).await?;
+    let state = PbsState::new(config);
+
+    let params = GetHeaderParams { slot: 0, parent_hash: B256::ZERO, pubkey: Default::default() };
+    info!("Streaming get header");
+    let bids: Vec<_> = get_header_stream(params, &HeaderMap::new(), &state)?.collect().await;
+
+    assert_eq!(bids.len(), 2);
+    assert!(bids.iter().all(|bid| bid.is_ok()));
+    assert_eq!(mock_state.received_get_header(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_health() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending health check");
+    let res = mock_validator.do_health().await?;
+
+    assert_eq!(res.status(), StatusCode::OK);
+    let health: HealthResponse = res.json().await?;
+    assert_eq!(health.chain, "holesky");
+    assert!(!health.version.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_header_wrong_proposer() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let mut config = setup_pbs(&mock_state, 1).await?;
+
+    let proposer = BlsPublicKey::repeat_byte(1);
+    let beacon_state = Arc::new(MockBeaconState::new(proposer));
+    let beacon_port = serve_on_free_port(mock_beacon_app_router(beacon_state)).await?;
+    let beacon_url = get_local_address(beacon_port).parse()?;
+    config.beacon_client = Some(BeaconApiClient::new(beacon_url, Duration::from_millis(2000))?);
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending get header with wrong proposer");
+    let slot = chain.current_slot();
+    let res = mock_validator.do_get_header_for_pubkey(slot, BlsPublicKey::repeat_byte(2)).await?;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(mock_state.received_get_header(), 0);
+
+    info!("Sending get header with expected proposer");
+    let res = mock_validator.do_get_header_for_pubkey(slot, proposer).await?;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(mock_state.received_get_header(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_relay_exclusion_list() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let mut config = setup_pbs(&mock_state, 2).await?;
+
+    // serve an exclusion list with the first relay
+    let exclusion_list = vec![config.relays[0].config.entry.url.clone()];
+    let app = Router::new().route("/", get(move || async move { Json(exclusion_list) }));
+    let list_port = serve_on_free_port(app).await?;
+
+    let mut pbs_config = (*config.pbs_config).clone();
+    pbs_config.relay_exclusion_list_url = Some(get_local_address(list_port).parse()?);
+    config.pbs_config = Arc::new(pbs_config);
+    // leaves some time to fetch the list too
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending get status");
+    let res = mock_validator.do_get_status().await?;
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(mock_state.received_get_status(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_header_slot_in_past() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending get header for a past slot");
+    let slot = chain.current_slot() - 1;
+    let res = mock_validator.do_get_header_for_pubkey(slot, BlsPublicKey::ZERO).await?;
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(mock_state.received_get_header(), 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_header_slot_too_far_in_future() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending get header for a slot too far in the future");
+    let slot = chain.current_slot() + 3;
+    let res = mock_validator.do_get_header_for_pubkey(slot, BlsPublicKey::ZERO).await?;
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(mock_state.received_get_header(), 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_heartbeat() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let mut config = setup_pbs(&mock_state, 1).await?;
+
+    // count received heartbeats
+    let heartbeats = Arc::new(AtomicU64::new(0));
+    let counter = heartbeats.clone();
+    let app = Router::new().route(
+        "/",
+        post(move |Json(heartbeat): Json<Heartbeat>| async move {
+            assert_eq!(heartbeat.chain, Chain::Holesky);
+            counter.fetch_add(1, Ordering::Relaxed);
+        }),
+    );
+    let heartbeat_port = serve_on_free_port(app).await?;
+
+    config.pbs_global.heartbeat_url = Some(get_local_address(heartbeat_port).parse()?);
+    // leaves some time to send the first heartbeat too
+    start_pbs(PbsState::new(config)).await?;
+
+    assert_eq!(heartbeats.load(Ordering::Relaxed), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stats() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending get status and a get header for a past slot");
+    let res = mock_validator.do_get_status().await?;
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = mock_validator.do_get_header_for_pubkey(0, BlsPublicKey::ZERO).await?;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let res = mock_validator.do_stats().await?;
+    assert_eq!(res.status(), StatusCode::OK);
+    let stats: StatsResponse = res.json().await?;
+    assert_eq!(stats.requests, 2);
+    assert_eq!(stats.errors, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_simulate_mode() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let mut config = setup_pbs(&mock_state, 1).await?;
+    let mut pbs_config = (*config.pbs_config).clone();
+    pbs_config.simulate_mode = true;
+    config.pbs_config = Arc::new(pbs_config);
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending status, register validator, get header and submit block in simulate mode");
+    assert_eq!(mock_validator.do_get_status().await?.status(), StatusCode::OK);
+    assert!(mock_validator.do_register_validator().await.is_ok());
+
+    let res =
+        mock_validator.do_get_header_for_pubkey(chain.current_slot(), BlsPublicKey::ZERO).await?;
+    assert_eq!(res.status(), StatusCode::OK);
+    let bid: GetHeaderResponse = res.json().await?;
+    assert_eq!(bid.value(), U256::from(1_000_000_000_000_000u64));
+
+    assert!(mock_validator.do_submit_block().await.is_ok());
+    assert_eq!(mock_state.received_get_status(), 0);
+    assert_eq!(mock_state.received_register_validator(), 0);
+    assert_eq!(mock_state.received_get_header(), 0);
+    assert_eq!(mock_state.received_submit_block(), 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_relay_list_file() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let mut config = setup_pbs(&mock_state, 1).await?;
+
+    // the first relay is also in the main config and should only be used once
+    let file_relays =
+        vec![config.relays[0].config.clone(), start_mock_relay(mock_state.clone()).await?];
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("relay-list.json");
+    std::fs::write(&path, serde_json::to_string(&file_relays)?)?;
+
+    config.pbs_global.relay_list_file = Some(path);
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending get status");
+    let res = mock_validator.do_get_status().await;
+
+    assert!(res.is_ok());
+    assert_eq!(mock_state.received_get_status(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_header_unknown_parent_hash() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let mut config = setup_pbs(&mock_state, 1).await?;
+
+    // execution client knowing the parent block only once `known` is set
+    let known = Arc::new(AtomicBool::new(false));
+    let is_known = known.clone();
+    let app = Router::new().route(
+        "/",
+        post(move || async move {
+            let block = serde_json::json!({
+                "hash": B256::ZERO,
+                "parentHash": B256::repeat_byte(1),
+                "gasLimit": "0x1c9c380",
+            });
+            let result = is_known.load(Ordering::Relaxed).then_some(block);
+            Json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+        }),
+    );
+    let execution_port = serve_on_free_port(app).await?;
+
+    let execution_url = get_local_address(execution_port).parse()?;
+    config.execution_client = Some(ExecutionApiClient::new(execution_url)?);
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    // an unknown parent may not be imported yet, so the check fails open
+    info!("Sending get header with unknown parent hash");
+    let slot = chain.current_slot();
+    let res = mock_validator.do_get_header_for_pubkey(slot, BlsPublicKey::ZERO).await?;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(mock_state.received_get_header(), 1);
+
+    info!("Sending get header with known parent hash");
+    known.store(true, Ordering::Relaxed);
+    let res = mock_validator.do_get_header_for_pubkey(slot, BlsPublicKey::ZERO).await?;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(mock_state.received_get_header(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_header_wrong_excess_blob_gas() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    // the parent is below the blob gas target, so the expected excess is 0
+    let bids = [1u64, 0].map(|excess_blob_gas| {
+        let mut bid = GetHeaderResponse::default();
+        bid.data.message.header.excess_blob_gas = excess_blob_gas;
+        bid
+    });
+    let mock_state = Arc::new(new_mock_state(chain).with_bids(bids));
+    let mut config = setup_pbs(&mock_state, 1).await?;
+
+    let app = Router::new().route(
+        "/",
+        post(|| async {
+            let block = serde_json::json!({
+                "hash": B256::ZERO,
+                "parentHash": B256::repeat_byte(1),
+                "gasLimit": "0x1c9c380",
+                "excessBlobGas": "0x0",
+                "blobGasUsed": "0x0",
+            });
+            Json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": block }))
+        }),
+    );
+    let execution_port = serve_on_free_port(app).await?;
+
+    let execution_url = get_local_address(execution_port).parse()?;
+    config.execution_client = Some(ExecutionApiClient::new(execution_url)?);
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+
+    info!("Sending get header with a wrong excess blob gas bid");
+    let slot = chain.current_slot();
+    let res = mock_validator.do_get_header_for_pubkey(slot, BlsPublicKey::ZERO).await?;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    info!("Sending get header with a valid excess blob gas bid");
+    let res = mock_validator.do_get_header_for_pubkey(slot, BlsPublicKey::ZERO).await?;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(mock_state.received_get_header(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_events_api() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let mut config = setup_pbs(&mock_state, 1).await?;
+    config.events_api_jwt = Some(Jwt("events-jwt".to_string()));
+    let url = format!("{}{EVENTS_PATH}", get_local_address(config.pbs_config.port));
+    let mock_validator = start_pbs(PbsState::new(config)).await?;
+    let client = reqwest::Client::new();
+
+    info!("Connecting to the events API with a wrong JWT");
+    let res = client.get(&url).bearer_auth("wrong-jwt").send().await?;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    let res = client.get(&url).send().await?;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    info!("Connecting to the events API with the right JWT");
+    let mut res = client.get(&url).bearer_auth("events-jwt").send().await?;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["content-type"], "text/event-stream");
+
+    assert_eq!(mock_validator.do_get_status().await?.status(), StatusCode::OK);
+    let chunk = tokio::time::timeout(Duration::from_secs(1), res.chunk()).await??;
+    let chunk = String::from_utf8(chunk.expect("stream ended").to_vec())?;
+    assert!(chunk.starts_with("data: \"GetStatusEvent\"\n\n"));
+    Ok(())
+}
+
+#[derive(Default)]
+struct RecordingLogger {
+    requests: AtomicU64,
+    responses: Mutex<Vec<ResponseSummary>>,
+}
+
+impl RequestLogger for RecordingLogger {
+    fn log_request(&self, _req: &RequestSummary) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn log_response(&self, res: &ResponseSummary) {
+        self.responses.lock().unwrap().push(res.clone());
+    }
+}
+
+#[tokio::test]
+async fn test_request_logger() -> Result<()> {
+    setup_test_env();
+    let chain = Chain::Holesky;
+
+    let mock_state = Arc::new(new_mock_state(chain));
+    let config = setup_pbs(&mock_state, 1).await?;
+    let logger = Arc::new(RecordingLogger::default());
+    let mock_validator =
+        start_pbs(PbsState::new(config).with_request_logger(logger.clone())).await?;
+
+    info!("Sending get header");
+    let slot = chain.current_slot();
+    mock_validator.do_get_header(slot).await?;
+
+    assert_eq!(logger.requests.load(Ordering::Relaxed), 1);
+    let responses = logger.responses.lock().unwrap();
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0].slot, Some(slot));
+    assert_eq!(responses[0].status, StatusCode::OK);
+    Ok(())
+}